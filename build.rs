@@ -0,0 +1,99 @@
+//! Generates one test per `[[case]]` in `data/examples/NN.toml`, or in the examples of
+//! `AOC_DATA_DIR` if it is set. The `solution!` macro includes the generated file of its day, see
+//! `src/template/examples.rs` for the format. Also records the compiler version and target
+//! features, which are stored with benchmarks.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+fn main() {
+    // mirrors `data_dir` in `src/template/paths.rs`.
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let data_dir = match env::var_os("AOC_DATA_DIR") {
+        Some(dir) if !dir.is_empty() => manifest_dir.join(dir),
+        _ => manifest_dir.join("data"),
+    };
+    let examples_dir = data_dir.join("examples");
+    println!("cargo::rerun-if-env-changed=AOC_DATA_DIR");
+    println!("cargo::rerun-if-changed={}", examples_dir.display());
+
+    // recorded with benchmarks, see `src/template/environment.rs`.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("-V")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    println!("cargo::rustc-env=AOC_RUSTC_VERSION={version}");
+    println!(
+        "cargo::rustc-env=AOC_TARGET_FEATURES={}",
+        env::var("CARGO_CFG_TARGET_FEATURE").unwrap_or_default()
+    );
+
+    let out_dir = Path::new(&env::var("OUT_DIR").unwrap()).join("example_cases");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    for day in 1..=25 {
+        let path = examples_dir.join(format!("{day:02}.toml"));
+        let names = fs::read_to_string(path)
+            .map(|src| case_names(&src))
+            .unwrap_or_default();
+        let tests: String = names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                format!(
+                    "#[test]\nfn {name}() {{\n    ::advent_of_code::template::check_example_case(solution(), DAY, {idx});\n}}\n"
+                )
+            })
+            .collect();
+        fs::write(out_dir.join(format!("{day}.rs")), tests).unwrap();
+    }
+}
+
+/// Test function names of the cases, in order. Mirrors the default names of
+/// `advent_of_code::template::ExampleCase`, and makes names unique and valid identifiers.
+fn case_names(src: &str) -> Vec<String> {
+    let mut names: Vec<Option<String>> = vec![];
+    for line in src.lines() {
+        let line = line.split_once(" #").map_or(line, |(line, _)| line).trim();
+        if line == "[[case]]" {
+            names.push(None);
+        } else if let (Some(name), Some((key, value))) = (names.last_mut(), line.split_once('='))
+            && key.trim() == "name"
+        {
+            *name = Some(value.trim().trim_matches('"').to_string());
+        }
+    }
+
+    let count = names.len();
+    let mut idents: Vec<String> = vec![];
+    for (idx, name) in names.into_iter().enumerate() {
+        let name = name.unwrap_or_else(|| {
+            if count == 1 {
+                "example".into()
+            } else {
+                format!("example_{}", idx + 1)
+            }
+        });
+        let mut ident: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if !ident.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            ident.insert_str(0, "case_");
+        }
+        if idents.contains(&ident) {
+            ident = format!("{ident}_{}", idx + 1);
+        }
+        idents.push(ident);
+    }
+    idents
+}
@@ -1,6 +1,6 @@
-use std::{io, time::Duration, u64};
+use std::{io, time::Duration};
 
-use advent_of_code::util::fast_parse;
+use advent_of_code::util::{fast_parse, intervals::IntervalSet};
 use ratatui::{
     Frame,
     buffer::Buffer,
@@ -13,30 +13,15 @@ use ratatui::{
 
 advent_of_code::solution!(5);
 
-#[derive(Debug)]
-struct MyRange {
-    start: u64,
-    end: u64,
-}
-
-impl MyRange {
-    fn len(&self) -> u64 {
-        1 + self.end - self.start
-    }
-    fn contains(&self, other: &u64) -> bool {
-        self.start <= *other && self.end >= *other
-    }
-}
-
-fn parse_fresh(mut input: &[u8]) -> (Vec<MyRange>, &[u8]) {
+fn parse_fresh(mut input: &[u8]) -> (IntervalSet<u64>, &[u8]) {
     let mut fresh = vec![];
     while input[0] != b'\n' {
         let (start, rem) = fast_parse(input);
         let (end, rem) = fast_parse(&rem[1..]);
-        fresh.push(MyRange { start, end });
+        fresh.push((start, end));
         input = &rem[1..];
     }
-    (fresh, input)
+    (fresh.into_iter().collect(), input)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -45,7 +30,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut fresh_count = 0u64;
     while !input.is_empty() {
         let (ingridient, rem) = fast_parse::<u64>(input);
-        if fresh.iter().any(|r| r.contains(&ingridient)) {
+        if fresh.contains(ingridient) {
             fresh_count += 1;
         }
         input = &rem[1..];
@@ -54,39 +39,8 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (mut fresh, _) = parse_fresh(input.as_bytes());
-    fresh.sort_unstable_by_key(|r| r.start);
-    // deduplicate ranges
-    let mut fresh_count = 0u64;
-    'outer: for i in 0..fresh.len() {
-        let (a, b) = fresh.split_at_mut(i + 1);
-        let this_range = &mut a[i];
-        if this_range.len() == 0 {
-            continue;
-        }
-        for other_range in b {
-            if this_range.end < other_range.start {
-                fresh_count += this_range.len();
-                continue 'outer;
-            }
-            dedup(this_range, other_range);
-        }
-        fresh_count += this_range.len();
-    }
-    Some(fresh_count)
-}
-
-fn dedup(r1: &mut MyRange, r2: &mut MyRange) {
-    // r2 cant end before r1 starts because the ranges are sorted
-    if r1.end < r2.start {
-        return;
-    }
-    r1.start = (r1.start).min(r2.start);
-    r1.end = (r1.end).max(r2.end);
-    *r2 = MyRange {
-        start: u64::MAX,
-        end: u64::MAX - 1,
-    };
+    let (fresh, _) = parse_fresh(input.as_bytes());
+    Some(fresh.total_len())
 }
 
 #[derive(Debug)]
@@ -230,16 +184,71 @@ pub fn part_two_tui(input: &str) -> io::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generators::day05;
+    use advent_of_code::template::differential::Differential;
+
+    /// Checks every id against every range.
+    fn part_one_naive(input: &str) -> Option<u64> {
+        let (ranges, ids) = input.split_once("\n\n").unwrap();
+        let ranges: Vec<(u64, u64)> = ranges
+            .lines()
+            .map(|line| {
+                let (start, end) = line.split_once('-').unwrap();
+                (start.parse().unwrap(), end.parse().unwrap())
+            })
+            .collect();
+        let fresh = ids
+            .lines()
+            .map(|id| id.parse::<u64>().unwrap())
+            .filter(|id| {
+                ranges
+                    .iter()
+                    .any(|(start, end)| (start..=end).contains(&id))
+            })
+            .count();
+        Some(fresh as u64)
+    }
+
+    /// Sweeps over the range boundaries, counting ids covered by at least one range.
+    fn part_two_sweep(input: &str) -> Option<u64> {
+        let (ranges, _) = input.split_once("\n\n").unwrap();
+        let mut events: Vec<(u64, i64)> = ranges
+            .lines()
+            .flat_map(|line| {
+                let (start, end) = line.split_once('-').unwrap();
+                let (start, end): (u64, u64) = (start.parse().unwrap(), end.parse().unwrap());
+                [(start, 1), (end + 1, -1)]
+            })
+            .collect();
+        events.sort_unstable();
+        let (mut fresh, mut depth, mut covered_since) = (0, 0, 0);
+        for (position, change) in events {
+            if depth == 0 && change > 0 {
+                covered_since = position;
+            }
+            depth += change;
+            if depth == 0 {
+                fresh += position - covered_since;
+            }
+        }
+        Some(fresh)
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
+    fn part_one_matches_naive_on_generated_inputs() {
+        Differential::new("part one")
+            .with("interval set", part_one)
+            .with("naive", part_one_naive)
+            .assert_agree_on_examples(DAY)
+            .assert_agree_on_sizes(200, day05::generate, 1..=40);
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
+    fn part_two_matches_sweep_on_generated_inputs() {
+        Differential::new("part two")
+            .with("interval set", part_two)
+            .with("sweep", part_two_sweep)
+            .assert_agree_on_examples(DAY)
+            .assert_agree_on_sizes(200, day05::generate, 1..=40);
     }
 }
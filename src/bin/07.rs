@@ -1,4 +1,4 @@
-use std::{io, mem::swap, time::Duration};
+use std::{collections::HashMap, io, mem::swap, time::Duration};
 
 use ratatui::{
     Frame,
@@ -38,66 +38,71 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(split)
 }
 
-// pub fn part_two(input: &str) -> Option<u64> {
-//     let input = input.as_bytes();
-//     let width = input.iter().position(|&c| c == b'\n').unwrap() + 1;
-//     let start_index = width / 2 - 1;
-//     debug_assert!(input[start_index] == b'S');
-//     let jump_size = 2 * width;
-//     let mut cache = HashMap::new();
-//     Some(beam_splitter(
-//         input,
-//         start_index + jump_size,
-//         jump_size,
-//         &mut cache,
-//     ))
-// }
-//
-// fn beam_splitter(
-//     input: &[u8],
-//     index: usize,
-//     jump_size: usize,
-//     cache: &mut HashMap<usize, u64>,
-// ) -> u64 {
-//     if let Some(answer) = cache.get(&index) {
-//         return *answer;
-//     }
-//     if index >= input.len() {
-//         return 1;
-//     }
-//     let answer = if input[index] == b'^' {
-//         beam_splitter(input, index + jump_size - 1, jump_size, cache)
-//             + beam_splitter(input, index + jump_size + 1, jump_size, cache)
-//     } else {
-//         beam_splitter(input, index + jump_size, jump_size, cache)
-//     };
-//     cache.insert(index, answer);
-//     answer
-// }
+/// Counts timelines recursively, memoized per splitter. Kept to check [`part_two`] against.
+#[cfg_attr(not(test), allow(dead_code))]
+fn part_two_memoized(input: &str) -> Option<u64> {
+    let input = input.as_bytes();
+    let width = input.iter().position(|&c| c == b'\n').unwrap() + 1;
+    let start_index = width / 2 - 1;
+    debug_assert!(input[start_index] == b'S');
+    let jump_size = 2 * width;
+    let mut cache = HashMap::new();
+    Some(beam_splitter(
+        input,
+        start_index + jump_size,
+        jump_size,
+        &mut cache,
+    ))
+}
 
-// pub fn part_two(input: &str) -> Option<u64> {
-//     let input = input.as_bytes();
-//     let width = input.iter().position(|&c| c == b'\n').unwrap() + 1;
-//     let start_index = width / 2 - 1;
-//     debug_assert!(input[start_index] == b'S');
-//     let mut beams = vec![0; width];
-//     beams[start_index] = 1;
-//     let mut next_beams = vec![0; width];
-//     for layer in input.chunks_exact(width).step_by(2).skip(1) {
-//         for (col, &content) in layer[..width].iter().enumerate() {
-//             let prev_beam = beams[col];
-//             if content == b'^' {
-//                 next_beams[col - 1] += prev_beam;
-//                 next_beams[col + 1] += prev_beam;
-//             } else {
-//                 next_beams[col] += prev_beam;
-//             }
-//         }
-//         swap(&mut beams, &mut next_beams);
-//         next_beams.fill(0);
-//     }
-//     Some(beams.iter().sum())
-// }
+#[cfg_attr(not(test), allow(dead_code))]
+fn beam_splitter(
+    input: &[u8],
+    index: usize,
+    jump_size: usize,
+    cache: &mut HashMap<usize, u64>,
+) -> u64 {
+    if let Some(answer) = cache.get(&index) {
+        return *answer;
+    }
+    if index >= input.len() {
+        return 1;
+    }
+    let answer = if input[index] == b'^' {
+        beam_splitter(input, index + jump_size - 1, jump_size, cache)
+            + beam_splitter(input, index + jump_size + 1, jump_size, cache)
+    } else {
+        beam_splitter(input, index + jump_size, jump_size, cache)
+    };
+    cache.insert(index, answer);
+    answer
+}
+
+/// Counts timelines with one counter per column. Kept to check [`part_two`] against.
+#[cfg_attr(not(test), allow(dead_code))]
+fn part_two_dense(input: &str) -> Option<u64> {
+    let input = input.as_bytes();
+    let width = input.iter().position(|&c| c == b'\n').unwrap() + 1;
+    let start_index = width / 2 - 1;
+    debug_assert!(input[start_index] == b'S');
+    let mut beams = vec![0; width];
+    beams[start_index] = 1;
+    let mut next_beams = vec![0; width];
+    for layer in input.chunks_exact(width).step_by(2).skip(1) {
+        for (col, &content) in layer[..width].iter().enumerate() {
+            let prev_beam = beams[col];
+            if content == b'^' {
+                next_beams[col - 1] += prev_beam;
+                next_beams[col + 1] += prev_beam;
+            } else {
+                next_beams[col] += prev_beam;
+            }
+        }
+        swap(&mut beams, &mut next_beams);
+        next_beams.fill(0);
+    }
+    Some(beams.iter().sum())
+}
 
 #[derive(Copy, Clone)]
 struct Beam {
@@ -135,16 +140,14 @@ pub fn part_two(input: &str) -> Option<u64> {
                     col: beam.col + 1,
                     particles: beam.particles,
                 });
-            } else if let Some(last_beam) = next_beams.last_mut() {
+            } else if let Some(last_beam) = next_beams.last_mut()
+                && last_beam.col == beam.col
+            {
+                // no splitter, merging into the right split of the previous beam
+                last_beam.particles += beam.particles;
+            } else {
                 // no splitter
-                if last_beam.col == beam.col {
-                    last_beam.particles += beam.particles;
-                } else {
-                    next_beams.push(Beam {
-                        col: beam.col,
-                        particles: beam.particles,
-                    });
-                }
+                next_beams.push(beam);
             }
         }
         swap(&mut beams, &mut next_beams);
@@ -294,16 +297,42 @@ pub fn part_two_tui(input: &str) -> io::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generators::day07;
+    use advent_of_code::template::differential::Differential;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+    fn part_two_keeps_beams_left_of_all_splitters() {
+        // the leftmost beam passes a row without a splitter before anything was added to it.
+        let input = "..S..\n.....\n.....\n.....\n..^..\n.....\n";
+        assert_eq!(part_two(input), Some(2));
+        let input = "...S...\n.......\n...^...\n.......\n.....^.\n.......\n";
+        assert_eq!(part_two(input), Some(2));
+    }
+
+    /// Removes a pair of splitter and empty rows, or a single splitter.
+    fn shrink_manifold(input: &str) -> Vec<String> {
+        let rows: Vec<&str> = input.lines().collect();
+        let mut candidates = vec![];
+        for layer in (2..rows.len()).step_by(2) {
+            let mut shrunk = rows.clone();
+            shrunk.drain(layer - 1..=layer);
+            candidates.push(shrunk.iter().map(|row| format!("{row}\n")).collect());
+        }
+        for (idx, _) in input.match_indices('^') {
+            candidates.push(format!("{}.{}", &input[..idx], &input[idx + 1..]));
+        }
+        candidates
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(40));
+    fn part_two_implementations_agree() {
+        Differential::new("part two")
+            .with("sparse beams", part_two)
+            .with("memoized", part_two_memoized)
+            .with("dense", part_two_dense)
+            .shrink_with(shrink_manifold)
+            .assert_agree_on_examples(DAY)
+            .assert_agree_on_input(DAY)
+            .assert_agree_on_sizes(500, day07::generate, 1..=12);
     }
 }
@@ -1,5 +1,6 @@
 use std::{collections::HashSet, io, mem::swap, time::Duration};
 
+use advent_of_code::template::{Params, Solution};
 use advent_of_code::util::fast_parse;
 use ratatui::{
     Frame,
@@ -15,7 +16,55 @@ use ratatui::{
 };
 use rayon::slice::ParallelSliceMut;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, Day08::default());
+
+struct Day08 {
+    /// How many of the closest pairs part 1 connects, 10 for the example.
+    to_connect: usize,
+}
+
+impl Default for Day08 {
+    fn default() -> Self {
+        Self { to_connect: 1000 }
+    }
+}
+
+struct Playground {
+    boxes: Vec<(i32, i32, i32)>,
+    distances: Vec<Distance>,
+}
+
+impl Solution for Day08 {
+    type Parsed<'a> = Playground;
+    type Answer1 = Option<u64>;
+    type Answer2 = Option<u64>;
+
+    fn configure(&mut self, params: &Params) {
+        self.to_connect = params.get_or("to_connect", self.to_connect);
+    }
+
+    fn parse(&self, input: &str) -> Playground {
+        let boxes = parse_input(input.as_bytes());
+        let distances = boxes_to_distances(&boxes);
+        Playground { boxes, distances }
+    }
+
+    fn part_one(&self, playground: &Playground) -> Option<u64> {
+        part_one(playground, self.to_connect)
+    }
+
+    fn part_two(&self, playground: &Playground) -> Option<u64> {
+        part_two(playground)
+    }
+
+    fn part_one_tui(&self, input: &str) -> io::Result<()> {
+        part_one_tui(input)
+    }
+
+    fn part_two_tui(&self, input: &str) -> io::Result<()> {
+        part_two_tui(input)
+    }
+}
 
 fn parse_input(mut input: &[u8]) -> Vec<(i32, i32, i32)> {
     let mut boxes = vec![];
@@ -92,14 +141,9 @@ fn mul_largest_three(circuits: Vec<HashSet<u32>>) -> u64 {
     (max1 * max2 * max3) as u64
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    part_one_inner(input, 1000)
-}
-
-fn part_one_inner(input: &str, to_connect: usize) -> Option<u64> {
-    let boxes = parse_input(input.as_bytes());
+fn part_one(playground: &Playground, to_connect: usize) -> Option<u64> {
+    let Playground { boxes, distances } = playground;
     let box_count = boxes.len();
-    let distances = boxes_to_distances(&boxes);
     let mut box_to_circuit: Vec<u32> = (0..box_count as u32).collect();
     let mut circuits: Vec<_> = (0..box_count as u32)
         .map(|b| {
@@ -133,10 +177,9 @@ fn part_one_inner(input: &str, to_connect: usize) -> Option<u64> {
     Some(mul_largest_three(circuits))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let boxes = parse_input(input.as_bytes());
+fn part_two(playground: &Playground) -> Option<u64> {
+    let Playground { boxes, distances } = playground;
     let box_count = boxes.len();
-    let distances = boxes_to_distances(&boxes);
     let mut box_to_circuit: Vec<u32> = (0..box_count as u32).collect();
     let mut circuits: Vec<_> = (0..box_count as u32).map(|b| vec![b; 1]).collect();
     for potential_connection in distances {
@@ -423,20 +466,3 @@ pub fn part_two_tui(input: &str) -> io::Result<()> {
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one_inner(&advent_of_code::template::read_file("examples", DAY), 10);
-        assert_eq!(result, Some(40));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(25272));
-    }
-}
@@ -1,6 +1,10 @@
 use std::{io, time::Duration};
 
-use advent_of_code::util::fast_parse;
+use advent_of_code::template::Solution;
+use advent_of_code::util::{
+    fast_parse,
+    geometry::{Point, RectilinearPolygon},
+};
 use ratatui::{
     Frame,
     buffer::Buffer,
@@ -14,106 +18,68 @@ use ratatui::{
     },
 };
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, Day09);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut input = input.as_bytes();
-    let mut tiles = vec![];
-    while !input.is_empty() {
-        let (x, rem) = fast_parse::<u64>(input);
-        let (y, rem) = fast_parse::<u64>(&rem[1..]);
-        input = &rem[1..];
-        tiles.push((x, y));
-    }
-    tiles
-        .iter()
-        .enumerate()
-        .flat_map(|(idx, a)| {
-            tiles[idx + 1..]
-                .iter()
-                .map(|b| {
-                    let (min_x, max_x) = if a.0 < b.0 { (a.0, b.0) } else { (b.0, a.0) };
-                    let (min_y, max_y) = if a.1 < b.1 { (a.1, b.1) } else { (b.1, a.1) };
-                    (min_x, min_y, max_x + 1, max_y + 1)
-                })
-                .map(|(x1, y1, x2, y2)| (x2 - x1) * (y2 - y1))
-        })
-        .max()
-}
+struct Day09;
 
-fn is_clockwise(p1: (i64, i64), p2: (i64, i64), p3: (i64, i64)) -> bool {
-    (p3.1 - p1.1) * (p2.0 - p1.0) > (p2.1 - p1.1) * (p3.0 - p1.0)
-}
+impl Solution for Day09 {
+    type Parsed<'a> = Vec<Point>;
+    type Answer1 = Option<u64>;
+    type Answer2 = Result<u64, &'static str>;
 
-fn intersect(p1: (i64, i64), p2: (i64, i64), p3: (i64, i64), p4: (i64, i64)) -> bool {
-    (is_clockwise(p1, p3, p4) != is_clockwise(p2, p3, p4))
-        && (is_clockwise(p1, p2, p3) != is_clockwise(p1, p2, p4))
-}
+    fn parse(&self, input: &str) -> Vec<Point> {
+        parse_tiles(input)
+    }
 
-fn any_lines_crossing(idx1: usize, idx2: usize, tiles: &[(i64, i64)]) -> bool {
-    let p1 = tiles[idx1];
-    let p2 = tiles[idx2];
-    for (idx3, &p3) in tiles.iter().enumerate() {
-        if idx3 == idx1 || idx3 == idx2 {
-            continue;
-        }
-        let idx4 = if idx3 == tiles.len() - 1 { 0 } else { idx3 + 1 };
-        if idx4 == idx1 || idx4 == idx2 {
-            continue;
-        }
-        let p4 = tiles[idx4];
-        if intersect(p1, p2, p3, p4) {
-            return true;
+    fn part_one(&self, tiles: &Vec<Point>) -> Option<u64> {
+        tiles
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, &a)| tiles[idx + 1..].iter().map(move |&b| area(a, b)))
+            .max()
+    }
+
+    fn part_two(&self, tiles: &Vec<Point>) -> Result<u64, &'static str> {
+        let polygon =
+            RectilinearPolygon::new(tiles).ok_or("red tiles do not form a rectilinear loop")?;
+        let mut areas = vec![];
+        for (idx1, &p1) in tiles.iter().enumerate() {
+            for &p2 in &tiles[idx1 + 1..] {
+                areas.push((p1, p2, area(p1, p2)));
+            }
         }
+        areas.sort_unstable_by_key(|a| u64::MAX - a.2);
+        areas
+            .into_iter()
+            .find(|&(p1, p2, _)| polygon.contains_rect(p1, p2))
+            .map(|(_, _, area)| area)
+            .ok_or("no rectangle fits inside the loop")
+    }
+
+    fn part_one_tui(&self, input: &str) -> io::Result<()> {
+        part_one_tui(input)
+    }
+
+    fn part_two_tui(&self, input: &str) -> io::Result<()> {
+        part_two_tui(input)
     }
-    false
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+fn parse_tiles(input: &str) -> Vec<Point> {
     let mut input = input.as_bytes();
     let mut tiles = vec![];
     while !input.is_empty() {
         let (x, rem) = fast_parse::<i64>(input);
         let (y, rem) = fast_parse::<i64>(&rem[1..]);
         input = &rem[1..];
-        tiles.push((x, y));
+        tiles.push(Point::new(x, y));
     }
-    let mut areas = vec![];
-    for (idx1, p1) in tiles.iter().enumerate() {
-        for (idx2, p2) in tiles[idx1 + 1..].iter().enumerate() {
-            areas.push((
-                idx1,
-                idx2 + idx1 + 1,
-                ((p1.0.abs_diff(p2.0)) + 1) * ((p1.1.abs_diff(p2.1)) + 1),
-            ));
-        }
-    }
-    areas.sort_unstable_by_key(|a| u64::MAX - a.2);
-    for (idx1, idx2, area) in areas {
-        let p1 = tiles[idx1];
-        let p2 = tiles[idx2];
-        let (min_x, max_x) = if p1.0 < p2.0 {
-            (p1.0, p2.0)
-        } else {
-            (p2.0, p1.0)
-        };
-        let (min_y, max_y) = if p1.1 < p2.1 {
-            (p1.1, p2.1)
-        } else {
-            (p2.1, p1.1)
-        };
-        if tiles
-            .iter()
-            .any(|&(x3, y3)| x3 > min_x && x3 < max_x && y3 > min_y && y3 < max_y)
-        {
-            continue;
-        }
-        if any_lines_crossing(idx1, idx2, &tiles) {
-            continue;
-        }
-        return Some(area);
-    }
-    unreachable!()
+    tiles
+}
+
+/// Number of tiles in the rectangle with opposite corners `a` and `b`.
+fn area(a: Point, b: Point) -> u64 {
+    (a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1)
 }
 
 #[derive(Debug)]
@@ -155,7 +121,8 @@ impl Widget for &Part1App<'_> {
 #[derive(Debug)]
 struct Part2App<'a> {
     input: &'a [u8],
-    tiles: Vec<(i64, i64)>,
+    tiles: Vec<Point>,
+    polygon: RectilinearPolygon,
     idx2: usize,
     idx1: usize,
     valid: bool,
@@ -168,18 +135,14 @@ impl<'a> Part2App<'a> {
     }
 
     fn new(input: &'a [u8]) -> Self {
-        let mut cursor = input;
-        let mut tiles = vec![];
-        while !cursor.is_empty() {
-            let (x, rem) = fast_parse::<i64>(cursor);
-            let (y, rem) = fast_parse::<i64>(&rem[1..]);
-            cursor = &rem[1..];
-            tiles.push((x, y));
-        }
+        let tiles = parse_tiles(std::str::from_utf8(input).unwrap());
+        let polygon =
+            RectilinearPolygon::new(&tiles).expect("tiles should form a rectilinear loop");
 
         Self {
             input,
             tiles,
+            polygon,
             idx1: 0usize,
             idx2: 0usize,
             valid: false,
@@ -210,24 +173,7 @@ fn part_two_single_check(state: &mut Part2App<'_>) {
     }
     let p1 = state.tiles[state.idx1];
     let p2 = state.tiles[state.idx2];
-    let (min_x, max_x) = if p1.0 < p2.0 {
-        (p1.0, p2.0)
-    } else {
-        (p2.0, p1.0)
-    };
-    let (min_y, max_y) = if p1.1 < p2.1 {
-        (p1.1, p2.1)
-    } else {
-        (p2.1, p1.1)
-    };
-    if state
-        .tiles
-        .iter()
-        .any(|&(x3, y3)| x3 > min_x && x3 < max_x && y3 > min_y && y3 < max_y)
-    {
-        return;
-    }
-    if any_lines_crossing(state.idx1, state.idx2, &state.tiles) {
+    if !state.polygon.contains_rect(p1, p2) {
         return;
     }
     state.valid = true;
@@ -235,10 +181,10 @@ fn part_two_single_check(state: &mut Part2App<'_>) {
 
 impl Widget for &Part2App<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let x = self.tiles[self.idx1].0 as f64;
-        let y = self.tiles[self.idx1].1 as f64;
-        let x2 = self.tiles[self.idx2].0 as f64;
-        let y2 = self.tiles[self.idx2].1 as f64;
+        let x = self.tiles[self.idx1].x as f64;
+        let y = self.tiles[self.idx1].y as f64;
+        let x2 = self.tiles[self.idx2].x as f64;
+        let y2 = self.tiles[self.idx2].y as f64;
         let title = Line::from("Part Two".bold());
         let status = Text::from(format!(
             "P1: ({},{}), P2: ({},{})\nReset: R Pause/Unpause: P, Toggle Skip To Next Valid: V\nCurrently {}",
@@ -265,19 +211,19 @@ impl Widget for &Part2App<'_> {
                 let mut prev_tile = self.tiles[0];
                 for curr_tile in self.tiles[1..].iter() {
                     ctx.draw(&canvas::Line {
-                        x1: prev_tile.0 as f64,
-                        y1: prev_tile.1 as f64,
-                        x2: curr_tile.0 as f64,
-                        y2: curr_tile.1 as f64,
+                        x1: prev_tile.x as f64,
+                        y1: prev_tile.y as f64,
+                        x2: curr_tile.x as f64,
+                        y2: curr_tile.y as f64,
                         color: ratatui::style::Color::White,
                     });
                     prev_tile = *curr_tile;
                 }
                 ctx.draw(&canvas::Line {
-                    x1: prev_tile.0 as f64,
-                    y1: prev_tile.1 as f64,
-                    x2: self.tiles[0].0 as f64,
-                    y2: self.tiles[0].1 as f64,
+                    x1: prev_tile.x as f64,
+                    y1: prev_tile.y as f64,
+                    x2: self.tiles[0].x as f64,
+                    y2: self.tiles[0].y as f64,
                     color: ratatui::style::Color::White,
                 });
                 let rect_color = if self.valid {
@@ -364,20 +310,3 @@ pub fn part_two_tui(input: &str) -> io::Result<()> {
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(50));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(24));
-    }
-}
@@ -1,6 +1,10 @@
 use std::{io, iter::from_fn, time::Duration};
 
-use advent_of_code::util::fast_parse;
+use advent_of_code::util::{
+    bits::{Bits, DynBitSet, solve_gf2},
+    fast_parse, ilp,
+};
+#[cfg(feature = "good-lp")]
 use good_lp::*;
 use ratatui::{
     Frame,
@@ -14,43 +18,39 @@ use ratatui::{
 
 advent_of_code::solution!(10);
 
-fn parse_light_requirement(mut input: &[u8]) -> (u32, &[u8]) {
+/// Lights and buttons are sets over the lights of a machine, which can be more than 64.
+type Lights = DynBitSet;
+
+fn parse_light_requirement(mut input: &[u8]) -> (Lights, &[u8]) {
     debug_assert!(input[0] == b'[');
     input = &input[1..];
-    let mut target = 0u32;
-    let mut lights = 0;
-    while input[0] != b']' {
-        lights += 1;
-        target >>= 1;
-        if input[0] == b'#' {
-            target |= 1 << 31;
+    let lights = input.iter().position(|&c| c == b']').unwrap();
+    let mut target = Lights::new(lights);
+    for (light, &c) in input[..lights].iter().enumerate() {
+        if c == b'#' {
+            target.set(light);
         }
-        input = &input[1..];
     }
-    (target >> (32 - lights), &input[1..])
+    (target, &input[lights + 1..])
 }
 
-fn parse_button_for_lights(mut input: &[u8]) -> (u32, &[u8]) {
+fn parse_button_for_lights(mut input: &[u8], lights: usize) -> (Lights, &[u8]) {
     debug_assert!(input[0] == b'(');
     input = &input[1..];
-    let mut button = 0u32;
+    let mut button = Lights::new(lights);
     while input[0] != b' ' {
         let (num, rem) = fast_parse::<u8>(input);
-        button |= 1 << num;
+        button.set(num as usize);
         input = &rem[1..];
     }
     (button, input)
 }
 
-fn find_minimum_presses_for_lights(target: u32, buttons: &[u32]) -> u64 {
-    if target == 0 {
-        return 0;
-    }
-    if buttons.is_empty() {
-        return u64::MAX / 2;
-    }
-    (find_minimum_presses_for_lights(target ^ buttons[0], &buttons[1..]) + 1)
-        .min(find_minimum_presses_for_lights(target, &buttons[1..]))
+/// [`None`] if the lights can not be reached, or if there are too many ways to reach them to
+/// find the fewest presses.
+fn find_minimum_presses_for_lights(target: &Lights, buttons: &[Lights]) -> Option<u64> {
+    let solution = solve_gf2(buttons, target)?;
+    Some(u64::from(solution.min_weight()?.count_ones()))
 }
 
 fn parse_button_for_joltage(mut input: &[u8]) -> (Vec<u32>, &[u8]) {
@@ -64,7 +64,29 @@ fn parse_button_for_joltage(mut input: &[u8]) -> (Vec<u32>, &[u8]) {
     }
     (button, input)
 }
-fn find_minimum_presses_for_joltage(buttons: &[Vec<u32>], joltage_reqs: &[u32]) -> u64 {
+
+#[cfg_attr(feature = "good-lp", allow(dead_code))]
+fn find_minimum_presses_for_joltage(buttons: &[Vec<u32>], joltage_reqs: &[u32]) -> Option<u64> {
+    let a: Vec<Vec<i64>> = (0..joltage_reqs.len() as u32)
+        .map(|req_index| {
+            buttons
+                .iter()
+                .map(|b| i64::from(b.contains(&req_index)))
+                .collect()
+        })
+        .collect();
+    let b: Vec<i64> = joltage_reqs.iter().map(|&req| i64::from(req)).collect();
+    // a button can not be pressed more often than the lowest requirement it counts towards.
+    let upper: Vec<i64> = buttons
+        .iter()
+        .map(|button| button.iter().map(|&i| b[i as usize]).min().unwrap_or(0))
+        .collect();
+    let presses = ilp::minimise(&a, &b, &vec![1; buttons.len()], &upper)?;
+    Some(presses.iter().sum::<i64>() as u64)
+}
+
+#[cfg(feature = "good-lp")]
+fn find_minimum_presses_for_joltage_lp(buttons: &[Vec<u32>], joltage_reqs: &[u32]) -> Option<u64> {
     let mut vars = ProblemVariables::new();
     let button_variable_defs = vec![variable().integer().min(0); buttons.len()];
     let button_variables: Vec<Variable> = vars.add_all(button_variable_defs);
@@ -86,68 +108,88 @@ fn find_minimum_presses_for_joltage(buttons: &[Vec<u32>], joltage_reqs: &[u32])
         problem = problem.with(req_equation.eq(req));
     }
     problem.set_parameter("log", "0");
-    let solution = problem.solve().unwrap();
-    button_variables
-        .iter()
-        .map(|&bv| solution.value(bv).round() as u64)
-        .sum()
+    let solution = problem.solve().ok()?;
+    Some(
+        button_variables
+            .iter()
+            .map(|&bv| solution.value(bv).round() as u64)
+            .sum(),
+    )
+}
+
+/// Uses the CBC backend of `good_lp` instead of [`ilp`] when built with `--features good-lp`.
+#[cfg(feature = "good-lp")]
+fn solve_joltage(buttons: &[Vec<u32>], joltage_reqs: &[u32]) -> Option<u64> {
+    find_minimum_presses_for_joltage_lp(buttons, joltage_reqs)
+}
+
+#[cfg(not(feature = "good-lp"))]
+fn solve_joltage(buttons: &[Vec<u32>], joltage_reqs: &[u32]) -> Option<u64> {
+    find_minimum_presses_for_joltage(buttons, joltage_reqs)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
     let mut input = input.as_bytes();
-    Some(
-        from_fn(|| {
-            if input.is_empty() {
-                return None;
-            }
-            // println!("{}", str::from_utf8(input).unwrap());
-            let (target, rem) = parse_light_requirement(input);
-            let mut buttons = vec![];
+    from_fn(|| {
+        if input.is_empty() {
+            return None;
+        }
+        // println!("{}", str::from_utf8(input).unwrap());
+        let (target, rem) = parse_light_requirement(input);
+        let mut buttons = vec![];
+        input = rem;
+        while input[1] != b'{' {
+            let (button, rem) = parse_button_for_lights(&input[1..], target.capacity());
+            buttons.push(button);
             input = rem;
-            while input[1] != b'{' {
-                let (button, rem) = parse_button_for_lights(&input[1..]);
-                buttons.push(button);
-                input = rem;
-            }
-            let next_line_start = input.iter().position(|&c| c == b'\n').unwrap();
-            input = &input[next_line_start + 1..];
-            Some((target, buttons))
-        })
-        .map(|(target, buttons)| find_minimum_presses_for_lights(target, &buttons))
-        .sum(),
-    )
+        }
+        let next_line_start = input.iter().position(|&c| c == b'\n').unwrap();
+        input = &input[next_line_start + 1..];
+        Some((target, buttons))
+    })
+    .map(|(target, buttons)| find_minimum_presses_for_lights(&target, &buttons))
+    .sum()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut input = input.as_bytes();
-    Some(
-        from_fn(|| {
-            if input.is_empty() {
-                return None;
-            }
-            // println!("{}", str::from_utf8(input).unwrap());
-            let first_button_start = input.iter().position(|&c| c == b' ').unwrap();
-            input = &input[first_button_start..];
-            let mut buttons = vec![];
-            while input[1] != b'{' {
-                let (button, rem) = parse_button_for_joltage(&input[1..]);
-                buttons.push(button);
-                input = rem;
-            }
-            input = &input[2..];
-            let mut joltage_reqs = Vec::with_capacity(32);
-            while input[0] != b'\n' {
-                let (num, rem) = fast_parse(input);
-                joltage_reqs.push(num);
-                input = &rem[1..];
-            }
+fn parse_joltage_machines(mut input: &[u8]) -> impl Iterator<Item = (Vec<Vec<u32>>, Vec<u32>)> {
+    from_fn(move || {
+        if input.is_empty() {
+            return None;
+        }
+        // println!("{}", str::from_utf8(input).unwrap());
+        let first_button_start = input.iter().position(|&c| c == b' ').unwrap();
+        input = &input[first_button_start..];
+        let mut buttons = vec![];
+        while input[1] != b'{' {
+            let (button, rem) = parse_button_for_joltage(&input[1..]);
+            buttons.push(button);
+            input = rem;
+        }
+        input = &input[2..];
+        let mut joltage_reqs = Vec::with_capacity(32);
+        while input[0] != b'\n' {
+            let (num, rem) = fast_parse(input);
+            joltage_reqs.push(num);
+            input = &rem[1..];
+        }
+
+        input = &input[1..];
+        Some((buttons, joltage_reqs))
+    })
+}
 
-            input = &input[1..];
-            Some((buttons, joltage_reqs))
+pub fn part_two(input: &str) -> Result<u64, String> {
+    parse_joltage_machines(input.as_bytes())
+        .enumerate()
+        .map(|(idx, (buttons, joltage_reqs))| {
+            solve_joltage(&buttons, &joltage_reqs).ok_or_else(|| {
+                format!(
+                    "no button presses reach the joltages of machine {}",
+                    idx + 1
+                )
+            })
         })
-        .map(|(buttons, joltage_reqs)| find_minimum_presses_for_joltage(&buttons, &joltage_reqs))
-        .sum(),
-    )
+        .sum()
 }
 
 #[derive(Debug)]
@@ -293,14 +335,21 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
+    fn test_more_than_64_lights() {
+        let target = format!("[{}#]", ".".repeat(69));
+        let input = format!("{target} (0,69) (0) {{1}}\n");
+        assert_eq!(part_one(&input), Some(2));
     }
 
+    #[cfg(feature = "good-lp")]
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(33));
+    fn test_exact_solver_matches_good_lp() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        for (buttons, joltage_reqs) in parse_joltage_machines(input.as_bytes()) {
+            assert_eq!(
+                find_minimum_presses_for_joltage(&buttons, &joltage_reqs),
+                find_minimum_presses_for_joltage_lp(&buttons, &joltage_reqs)
+            );
+        }
     }
 }
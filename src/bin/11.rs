@@ -1,5 +1,6 @@
-use std::{arch::x86_64::_pext_u32, io, time::Duration};
+use std::{io, time::Duration};
 
+use advent_of_code::util::bits::pext;
 use ratatui::{
     Frame,
     buffer::Buffer,
@@ -13,8 +14,8 @@ use ratatui::{
 advent_of_code::solution!(11);
 
 fn read_label_code(input: &[u8]) -> u32 {
-    let raw_code = unsafe { (input.as_ptr() as *const u32).read_unaligned() };
-    unsafe { _pext_u32(raw_code, 0b00000000_00011111_00011111_00011111) }
+    let raw_code = u32::from_le_bytes(input[..4].try_into().unwrap());
+    pext(raw_code, 0b00000000_00011111_00011111_00011111)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -256,22 +257,3 @@ pub fn part_two_tui(input: &str) -> io::Result<()> {
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(2));
-    }
-}
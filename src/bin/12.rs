@@ -1,5 +1,8 @@
 use std::{io, time::Duration};
 
+use advent_of_code::template::{
+    InputError, RawSolution, check_grid, check_lines, match_pattern, sections,
+};
 use advent_of_code::util::fast_parse;
 use ratatui::{
     Frame,
@@ -11,7 +14,57 @@ use ratatui::{
     widgets::{Block, BorderType, Paragraph, Widget, canvas::Canvas},
 };
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, Day12);
+
+struct Day12;
+
+impl RawSolution for Day12 {
+    type Answer1 = Option<u64>;
+    type Answer2 = Option<u64>;
+
+    /// Shape sections of `i:` and a grid, then one `WxH: n n ...` line per region with a count
+    /// for every shape.
+    fn validate(&self, input: &str) -> Result<(), InputError> {
+        let sections = sections(input);
+        let Some(((regions_line, regions), shapes)) = sections.split_last() else {
+            return Err(InputError::new("input is empty"));
+        };
+        if shapes.is_empty() {
+            return Err(InputError::new(
+                "expected present shapes before the regions",
+            ));
+        }
+        for (idx, &(line, shape)) in shapes.iter().enumerate() {
+            let (header, grid) = shape.split_once('\n').unwrap_or((shape, ""));
+            let index = match_pattern(header, "{}:")
+                .map_err(|message| InputError::at_line(line, message))?;
+            if index != [idx as u64] {
+                return Err(InputError::at_line(line, format!("expected shape {idx}")));
+            }
+            check_grid(grid, line + 1, b"#.")?;
+        }
+        let pattern = format!("{{}}x{{}}:{}", " {}".repeat(shapes.len()));
+        check_lines(regions, *regions_line, |line| {
+            match_pattern(line, &pattern).map(|_| ())
+        })
+    }
+
+    fn part_one(&self, input: &str) -> Option<u64> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Option<u64> {
+        part_two(input)
+    }
+
+    fn part_one_tui(&self, input: &str) -> io::Result<()> {
+        part_one_tui(input)
+    }
+
+    fn part_two_tui(&self, input: &str) -> io::Result<()> {
+        part_two_tui(input)
+    }
+}
 
 // struct Present {
 //     shape: [u64; 3],
@@ -36,8 +89,8 @@ advent_of_code::solution!(12);
 //     input = &input[3..];
 //     let mut area = 0;
 //     let shape: [u64; 3] = from_fn(|_| {
-//         let line_as_u32 = unsafe { (input.as_ptr() as *const u32).read_unaligned() };
-//         let line = unsafe { _pext_u32(line_as_u32, 0b00000000_00000001_00000001_00000001) } as u64;
+//         let line_as_u32 = u32::from_le_bytes(input[..4].try_into().unwrap());
+//         let line = pext(line_as_u32, 0b00000000_00000001_00000001_00000001) as u64;
 //         area += line.count_ones() as u64;
 //         input = &input[4..];
 //         line
@@ -83,17 +136,20 @@ advent_of_code::solution!(12);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let mut valid = 0u64;
-    let mut input = &input.as_bytes()[96..];
+    // the regions follow the last blank line, after the present shapes.
+    let regions_start = input.rfind("\n\n").map_or(0, |idx| idx + 2);
+    let mut input = &input.as_bytes()[regions_start..];
     while !input.is_empty() {
         let (width, rem) = fast_parse::<u32>(input);
         let (height, rem) = fast_parse::<u32>(&rem[1..]);
-        input = &rem[2..];
+        input = &rem[1..];
         let mut presents_to_fit = 0;
-        for _ in 0..6 {
-            let (num, rem) = fast_parse::<u32>(input);
-            input = &rem[1..];
+        while input[0] == b' ' {
+            let (num, rem) = fast_parse::<u32>(&input[1..]);
+            input = rem;
             presents_to_fit += num;
         }
+        input = &input[1..];
         if width * height >= presents_to_fit * 9 {
             valid += 1;
         }
@@ -242,20 +298,3 @@ pub fn part_two_tui(input: &str) -> io::Result<()> {
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-}
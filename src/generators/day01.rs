@@ -0,0 +1,14 @@
+//! Dial rotations, one `L<n>` or `R<n>` per line.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 4000;
+
+/// `size` rotations of up to 999 clicks.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let direction = if rng.chance(1, 2) { 'L' } else { 'R' };
+            format!("{direction}{}\n", rng.range(1..=999))
+        })
+        .collect()
+}
@@ -0,0 +1,17 @@
+//! Product id ranges, `start-end` separated by commas on a single line.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 35;
+
+/// `size` ranges of up to ten digit ids, each spanning at most 100000 ids.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let ranges: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let digits = rng.range(1..=10) as u32;
+            let start = rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+            let end = start + rng.below(100_000);
+            format!("{start}-{end}")
+        })
+        .collect();
+    format!("{}\n", ranges.join(","))
+}
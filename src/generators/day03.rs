@@ -0,0 +1,17 @@
+//! Battery banks, one line of joltage digits `1`-`9` per bank.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 200;
+
+/// `size` banks of 100 batteries.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut bank: String = (0..100)
+                .map(|_| char::from(b'1' + rng.below(9) as u8))
+                .collect();
+            bank.push('\n');
+            bank
+        })
+        .collect()
+}
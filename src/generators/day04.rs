@@ -0,0 +1,17 @@
+//! A square grid of paper rolls, `@` for a roll and `.` for an empty spot.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 137;
+
+/// A `size` by `size` grid, two thirds of it rolls.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row: String = (0..size)
+                .map(|_| if rng.chance(2, 3) { '@' } else { '.' })
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
@@ -0,0 +1,49 @@
+//! Fresh ingredient id ranges, a blank line, and the available ingredient ids.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 180;
+
+/// `size` ranges, many of them overlapping, nested in or adjacent to an earlier one, followed by
+/// `5 * size` ids of which about half are fresh.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const MAX_ID: u64 = 1_000_000_000_000_000;
+    let mut ranges: Vec<(u64, u64)> = vec![];
+    for _ in 0..size.max(1) {
+        let range = match ranges.get(rng.below(ranges.len() as u64 + 1) as usize) {
+            // nested
+            Some(&(start, end)) if rng.chance(1, 4) => {
+                let a = rng.range(start..=end);
+                (a, rng.range(a..=end))
+            }
+            // overlapping
+            Some(&(start, end)) if rng.chance(1, 3) => {
+                let a = rng.range(start..=end);
+                (a, a + rng.below(10 * (end - start + 1)))
+            }
+            // adjacent
+            Some(&(_, end)) if rng.chance(1, 2) => (end + 1, end + 1 + rng.below(1_000_000)),
+            _ => {
+                let start = rng.below(MAX_ID);
+                (start, start + rng.below(10_000_000_000_000))
+            }
+        };
+        ranges.push(range);
+    }
+    rng.shuffle(&mut ranges);
+
+    let mut input: String = ranges
+        .iter()
+        .map(|(start, end)| format!("{start}-{end}\n"))
+        .collect();
+    input.push('\n');
+    for _ in 0..5 * size.max(1) {
+        let id = if rng.chance(1, 2) {
+            let (start, end) = ranges[rng.below(ranges.len() as u64) as usize];
+            rng.range(start..=end)
+        } else {
+            rng.below(MAX_ID)
+        };
+        input.push_str(&format!("{id}\n"));
+    }
+    input
+}
@@ -0,0 +1,41 @@
+//! Rows of numbers laid out in aligned columns, one problem per column block, and a last row
+//! with each problem's operator under its first column.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// `size` problems of three or four numbers with up to four digits, each problem's numbers
+/// aligned either left or right.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let rows = rng.range(3..=4) as usize;
+    let mut lines = vec![String::new(); rows + 1];
+    for problem in 0..size.max(1) {
+        let width = rng.range(1..=4) as usize;
+        let left_aligned = rng.chance(1, 2);
+        if problem > 0 {
+            lines.iter_mut().for_each(|line| line.push(' '));
+        }
+        // one number has the full width, so the column block is exactly `width` wide.
+        let widest = rng.below(rows as u64) as usize;
+        for (row, line) in lines[..rows].iter_mut().enumerate() {
+            let digits = if row == widest {
+                width
+            } else {
+                rng.range(1..=width as u64) as usize
+            };
+            let number: String = (0..digits)
+                .map(|_| char::from(b'1' + rng.below(9) as u8))
+                .collect();
+            if left_aligned {
+                line.push_str(&format!("{number:<width$}"));
+            } else {
+                line.push_str(&format!("{number:>width$}"));
+            }
+        }
+        let operator = if rng.chance(1, 2) { '+' } else { '*' };
+        lines[rows].push_str(&format!("{operator:<width$}"));
+    }
+    let mut input: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    input.push('\n');
+    input
+}
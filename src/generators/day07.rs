@@ -0,0 +1,32 @@
+//! A tachyon manifold: a row with the start `S` in the middle, then alternating empty rows and
+//! rows of splitters `^`.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 70;
+
+/// `size` splitter rows. Like the real input, the splitters of row `k` lie within `k` columns of
+/// the start, on every other column, and some of them are missing.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let len = 2 * size + 1;
+    let middle = len / 2;
+    let mut rows = vec![format!("{}S{}", ".".repeat(middle), ".".repeat(middle))];
+    for layer in 0..size {
+        rows.push(".".repeat(len));
+        rows.push(
+            (0..len)
+                .map(|col| {
+                    let offset = col.abs_diff(middle);
+                    let reachable = offset <= layer && (layer - offset) % 2 == 0;
+                    if reachable && rng.chance(3, 4) {
+                        '^'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect(),
+        );
+    }
+    rows.push(".".repeat(len));
+    rows.iter().map(|row| format!("{row}\n")).collect()
+}
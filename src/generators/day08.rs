@@ -0,0 +1,18 @@
+//! Junction box positions, one `x,y,z` per line.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// `size` boxes with coordinates below 100000.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{},{},{}\n",
+                rng.below(100_000),
+                rng.below(100_000),
+                rng.below(100_000)
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,46 @@
+//! Red tiles, one `x,y` per line, in order around a rectilinear loop.
+use std::collections::BTreeSet;
+
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 125;
+
+/// A loop around `size` side by side columns, each with its own top and bottom, so the loop is
+/// concave at both sides. Coordinates stay below 100000.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const MAX: u64 = 100_000;
+    let columns = size.clamp(1, 10_000);
+    let mut xs = BTreeSet::new();
+    while xs.len() < columns + 1 {
+        xs.insert(rng.range(1..=MAX - 1));
+    }
+    let xs: Vec<u64> = xs.into_iter().collect();
+    let middle = MAX / 2;
+    let distinct_from = |rng: &mut Rng, previous: Option<u64>, low: u64, high: u64| loop {
+        let value = rng.range(low..=high);
+        if previous != Some(value) {
+            return value;
+        }
+    };
+    let mut tops: Vec<u64> = vec![];
+    let mut bottoms: Vec<u64> = vec![];
+    for _ in 0..columns {
+        let top = distinct_from(rng, tops.last().copied(), middle + 1, MAX - 1);
+        let bottom = distinct_from(rng, bottoms.last().copied(), 1, middle - 1);
+        tops.push(top);
+        bottoms.push(bottom);
+    }
+
+    let mut tiles = vec![(xs[0], bottoms[0]), (xs[0], tops[0])];
+    for col in 1..columns {
+        tiles.push((xs[col], tops[col - 1]));
+        tiles.push((xs[col], tops[col]));
+    }
+    tiles.push((xs[columns], tops[columns - 1]));
+    tiles.push((xs[columns], bottoms[columns - 1]));
+    for col in (1..columns).rev() {
+        tiles.push((xs[col], bottoms[col]));
+        tiles.push((xs[col], bottoms[col - 1]));
+    }
+    tiles.iter().map(|(x, y)| format!("{x},{y}\n")).collect()
+}
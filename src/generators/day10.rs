@@ -0,0 +1,62 @@
+//! Machines, one per line: the indicator light diagram in brackets, the wiring of each button in
+//! parentheses and the joltage requirements in braces.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 180;
+
+/// `size` machines with up to ten lights and a few more buttons than lights. Both the light
+/// pattern and the joltages are reachable, as they are made by pressing buttons.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| machine(rng)).collect()
+}
+
+fn machine(rng: &mut Rng) -> String {
+    let lights = rng.range(3..=10) as usize;
+    let button_count = lights + rng.below(4) as usize;
+    let mut buttons: Vec<Vec<usize>> = vec![];
+    while buttons.len() < button_count {
+        let mut wiring: Vec<usize> = (0..lights).filter(|_| rng.chance(2, 5)).collect();
+        if wiring.is_empty() {
+            wiring.push(rng.below(lights as u64) as usize);
+        }
+        if !buttons.contains(&wiring) {
+            buttons.push(wiring);
+        }
+    }
+    // every light is wired to some button.
+    for light in 0..lights {
+        if !buttons.iter().any(|wiring| wiring.contains(&light)) {
+            let button = rng.below(button_count as u64) as usize;
+            buttons[button].push(light);
+            buttons[button].sort_unstable();
+        }
+    }
+
+    let mut pattern = vec!['.'; lights];
+    let mut joltages = vec![0u64; lights];
+    for wiring in &buttons {
+        let presses = rng.below(13);
+        let toggled = rng.chance(1, 2);
+        for &light in wiring {
+            joltages[light] += presses;
+            if toggled {
+                pattern[light] = if pattern[light] == '.' { '#' } else { '.' };
+            }
+        }
+    }
+
+    let pattern: String = pattern.into_iter().collect();
+    let buttons: Vec<String> = buttons
+        .iter()
+        .map(|wiring| {
+            let lights: Vec<String> = wiring.iter().map(usize::to_string).collect();
+            format!("({})", lights.join(","))
+        })
+        .collect();
+    let joltages: Vec<String> = joltages.iter().map(u64::to_string).collect();
+    format!(
+        "[{pattern}] {} {{{}}}\n",
+        buttons.join(" "),
+        joltages.join(",")
+    )
+}
@@ -0,0 +1,69 @@
+//! Devices and their outputs, one `aaa: bbb ccc` per line, forming a directed acyclic graph
+//! that ends in `out`.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 600;
+
+const NAMED: [&str; 5] = ["svr", "you", "fft", "dac", "out"];
+const LAYERS: usize = 32;
+
+/// `size` devices in layers, each with one to three outputs in the next few layers. `svr` is in
+/// the first layer, `you`, `fft` and `dac` in between and `out` after the last one.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(NAMED.len());
+    let mut names: Vec<String> = NAMED.iter().map(|name| name.to_string()).collect();
+    while names.len() < size {
+        let name: String = (0..3)
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    // `out` is past the last layer, the named devices are spread over the layers.
+    let mut layers = vec![0, LAYERS / 4, LAYERS / 3, 2 * LAYERS / 3, LAYERS];
+    layers.extend((NAMED.len()..size).map(|_| rng.below(LAYERS as u64) as usize));
+    let mut by_layer: Vec<Vec<usize>> = vec![vec![]; LAYERS + 1];
+    for (device, &layer) in layers.iter().enumerate() {
+        by_layer[layer].push(device);
+    }
+
+    // a chain through one device of every layer, passing all the named ones, so there are
+    // paths from `svr` and `you` to `out` through `fft` and `dac`.
+    let mut chained: Vec<Option<usize>> = vec![None; size];
+    let chain: Vec<usize> = by_layer
+        .iter()
+        .filter(|devices| !devices.is_empty())
+        .map(|devices| match devices.iter().find(|&&d| d < NAMED.len()) {
+            Some(&named) => named,
+            None => devices[rng.below(devices.len() as u64) as usize],
+        })
+        .collect();
+    for link in chain.windows(2) {
+        chained[link[0]] = Some(link[1]);
+    }
+
+    let mut lines: Vec<String> = (0..size)
+        .filter(|&device| names[device] != "out")
+        .map(|device| {
+            let mut outputs: Vec<usize> = chained[device].into_iter().collect();
+            for _ in 0..rng.range(1..=3) {
+                // the closest non-empty layer after a random distance, which `out` ends.
+                let target_layer = (layers[device] + rng.range(1..=3) as usize).min(LAYERS);
+                let candidates = by_layer[target_layer..]
+                    .iter()
+                    .find(|devices| !devices.is_empty())
+                    .unwrap();
+                let output = candidates[rng.below(candidates.len() as u64) as usize];
+                if !outputs.contains(&output) {
+                    outputs.push(output);
+                }
+            }
+            let outputs: Vec<&str> = outputs.iter().map(|&o| names[o].as_str()).collect();
+            format!("{}: {}\n", names[device], outputs.join(" "))
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
@@ -0,0 +1,51 @@
+//! Six present shapes, `i:` followed by a 3x3 grid of `#` and `.`, then one region per line,
+//! `WxH:` followed by how many of each present have to fit under the tree.
+use crate::util::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// Six random shapes and `size` regions. Like in the real input, about half of the regions fit
+/// every present in its own 3x3 block and the others have fewer cells than the presents cover.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    let mut areas = vec![];
+    for idx in 0..6 {
+        let mut shape = [[false; 3]; 3];
+        let mut area = 0;
+        while area < 5 {
+            let (row, col) = (rng.below(3) as usize, rng.below(3) as usize);
+            if !shape[row][col] {
+                shape[row][col] = true;
+                area += 1;
+            }
+        }
+        areas.push(area);
+        input.push_str(&format!("{idx}:\n"));
+        for row in shape {
+            input.extend(row.iter().map(|&filled| if filled { '#' } else { '.' }));
+            input.push('\n');
+        }
+        input.push('\n');
+    }
+    for _ in 0..size {
+        let (width, height) = (rng.range(35..=50), rng.range(35..=50));
+        let mut counts = [0u64; 6];
+        if rng.chance(1, 2) {
+            // roomy: every present fits in its own 3x3 block.
+            for _ in 0..rng.range(1..=(width / 3) * (height / 3)) {
+                counts[rng.below(6) as usize] += 1;
+            }
+        } else {
+            // crowded: the presents cover more cells than the region has.
+            let mut filled = 0;
+            while filled <= width * height {
+                let present = rng.below(6) as usize;
+                counts[present] += 1;
+                filled += areas[present];
+            }
+        }
+        let counts: Vec<String> = counts.iter().map(u64::to_string).collect();
+        input.push_str(&format!("{width}x{height}: {}\n", counts.join(" ")));
+    }
+    input
+}
@@ -0,0 +1,74 @@
+//! Random inputs following each puzzle's input grammar, for stress tests and for benchmarking how
+//! solutions scale. Every day has a module with a `generate(rng, size)` function and the
+//! `DEFAULT_SIZE` matching the real input, see `cargo generate`.
+use crate::template::Day;
+use crate::util::rng::Rng;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+
+/// The input generator of a day.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    pub generate: fn(&mut Rng, usize) -> String,
+    /// Size of the real input.
+    pub default_size: usize,
+    /// What the size counts, e.g. `rotations`.
+    pub unit: &'static str,
+}
+
+/// The generator of `day`, or [`None`] if the day has none.
+pub fn generator(day: Day) -> Option<Generator> {
+    let (generate, default_size, unit): (fn(&mut Rng, usize) -> String, _, _) =
+        match day.into_inner() {
+            1 => (day01::generate, day01::DEFAULT_SIZE, "rotations"),
+            2 => (day02::generate, day02::DEFAULT_SIZE, "ranges"),
+            3 => (day03::generate, day03::DEFAULT_SIZE, "banks"),
+            4 => (day04::generate, day04::DEFAULT_SIZE, "rows and columns"),
+            5 => (day05::generate, day05::DEFAULT_SIZE, "ranges"),
+            6 => (day06::generate, day06::DEFAULT_SIZE, "problems"),
+            7 => (day07::generate, day07::DEFAULT_SIZE, "splitter rows"),
+            8 => (day08::generate, day08::DEFAULT_SIZE, "junction boxes"),
+            9 => (day09::generate, day09::DEFAULT_SIZE, "columns"),
+            10 => (day10::generate, day10::DEFAULT_SIZE, "machines"),
+            11 => (day11::generate, day11::DEFAULT_SIZE, "devices"),
+            12 => (day12::generate, day12::DEFAULT_SIZE, "regions"),
+            _ => return None,
+        };
+    Some(Generator {
+        generate,
+        default_size,
+        unit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generator;
+    use crate::template::Day;
+    use crate::util::rng::Rng;
+
+    #[test]
+    fn generators_are_reproducible_and_newline_terminated() {
+        for day in (1..=25).filter_map(Day::new) {
+            let Some(generator) = generator(day) else {
+                continue;
+            };
+            for size in [1, 5, 50] {
+                let input = (generator.generate)(&mut Rng::new(42), size);
+                assert!(input.ends_with('\n'), "day {day} with size {size}");
+                assert_eq!(input, (generator.generate)(&mut Rng::new(42), size));
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod generators;
 pub mod template;
 pub mod util;
 
@@ -1,40 +1,71 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time, tui};
+use advent_of_code::template::RunOptions;
+use advent_of_code::template::commands::{
+    all, download, generate, inputs, read, scaffold, solve, stars, time, tui,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
-#[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::scaffold::DEFAULT_TEMPLATE;
+    use advent_of_code::template::export::ExportFormat;
+    use advent_of_code::template::{Day, DayRange, DaySelection};
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            encrypt: bool,
+        },
+        Generate {
+            day: Day,
+            size: Option<usize>,
+            seed: Option<u64>,
+            out: Option<String>,
+        },
+        Inputs {
+            encrypt: bool,
+            day: Option<Day>,
         },
         Read {
             day: Day,
         },
         Scaffold {
-            day: Day,
+            days: DayRange,
+            template: String,
             download: bool,
             overwrite: bool,
         },
+        Stars,
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            allocations: bool,
+            profile_cpu: bool,
             submit: Option<u8>,
+            input: Option<String>,
         },
         All {
+            selection: DaySelection,
             release: bool,
+            allocations: bool,
+            jobs: usize,
+            part: Option<u8>,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            selection: DaySelection,
+            part: Option<u8>,
             store: bool,
+            profile: String,
+            allocations: bool,
+            counters: bool,
+            pin_cpu: Option<usize>,
+            export: Option<ExportFormat>,
+            out: Option<String>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -48,35 +79,95 @@ mod args {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                let release = args.contains("--release");
+                let allocations = args.contains("--allocations");
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let part = parse_part(&mut args)?;
+                AppArguments::All {
+                    selection: parse_selection(&mut args)?,
+                    release,
+                    allocations,
+                    jobs,
+                    part,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let counters = args.contains("--counters");
+                let pin_cpu = args.opt_value_from_str("--pin")?;
+                if args.opt_value_from_str::<_, usize>("--jobs")?.is_some() {
+                    eprintln!(
+                        "`cargo time` runs one day at a time so days do not skew each other's timings, `--jobs` is only supported by `cargo all`."
+                    );
+                    process::exit(1);
+                }
+                let profile = args
+                    .opt_value_from_str("--profile")?
+                    .unwrap_or_else(|| "release".to_string());
+                let allocations = args.contains("--allocations");
+                let export = args.opt_value_from_str("--export")?;
+                let out = args.opt_value_from_str("--out")?;
+                let part = parse_part(&mut args)?;
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    selection: parse_selection(&mut args)?,
+                    part,
                     store,
+                    profile,
+                    allocations,
+                    counters,
+                    pin_cpu,
+                    export,
+                    out,
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                encrypt: args.contains("--encrypt"),
             },
+            Some("generate") => AppArguments::Generate {
+                day: args.free_from_str()?,
+                size: args.opt_value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?,
+                out: args.opt_value_from_str("--out")?,
+            },
+            Some("inputs") => {
+                let encrypt = match args.free_from_str::<String>()?.as_str() {
+                    "encrypt" => true,
+                    "decrypt" => false,
+                    x => {
+                        eprintln!("Unknown inputs command: {x}. Expected `encrypt` or `decrypt`.");
+                        process::exit(1);
+                    }
+                };
+                AppArguments::Inputs {
+                    encrypt,
+                    day: args.opt_free_from_str()?,
+                }
+            }
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                days: args.free_from_str()?,
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string()),
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
+            Some("stars") => AppArguments::Stars,
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                allocations: args.contains("--allocations"),
+                profile_cpu: args.contains("--profile-cpu"),
+                input: args.opt_value_from_str("--input")?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -101,6 +192,34 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses `--part 1|2`.
+    fn parse_part(args: &mut pico_args::Arguments) -> Result<Option<u8>, pico_args::Error> {
+        let part = args.opt_value_from_str("--part")?;
+        if part.is_some_and(|part| part != 1 && part != 2) {
+            eprintln!("Part must be 1 or 2");
+            process::exit(1);
+        }
+        Ok(part)
+    }
+
+    /// Parses the filters of `all` and `time`, followed by an optional set of days like
+    /// `3,5,8-12`.
+    fn parse_selection(args: &mut pico_args::Arguments) -> Result<DaySelection, pico_args::Error> {
+        Ok(DaySelection {
+            only_failing: args.contains("--only-failing"),
+            only_changed: args.contains("--only-changed"),
+            slowest: args.opt_value_from_str("--slowest")?,
+            days: args.opt_free_from_str()?,
+        })
+    }
+}
+
+fn read_template(name: &str) -> String {
+    scaffold::read_template(name).unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        process::exit(1);
+    })
 }
 
 fn main() {
@@ -110,32 +229,99 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::All {
+                selection,
+                release,
+                allocations,
+                jobs,
+                part,
+            } => all::handle(
+                &selection,
+                RunOptions {
+                    profile: release.then_some("release"),
+                    allocations,
+                    jobs,
+                    part,
+                    ..RunOptions::default()
+                },
+            ),
+            AppArguments::Time {
+                selection,
+                part,
+                all,
+                store,
+                profile,
+                allocations,
+                counters,
+                pin_cpu,
+                export,
+                out,
+            } => time::handle(
+                &selection,
+                all,
+                store,
+                RunOptions {
+                    profile: Some(&profile),
+                    allocations,
+                    counters,
+                    pin_cpu,
+                    part,
+                    ..RunOptions::default()
+                },
+                export,
+                out,
+            ),
+            AppArguments::Download { day, encrypt } => {
+                download::handle(day);
+                if encrypt {
+                    inputs::handle(true, Some(day));
+                }
+            }
+            AppArguments::Generate {
+                day,
+                size,
+                seed,
+                out,
+            } => generate::handle(day, size, seed, out),
+            AppArguments::Inputs { encrypt, day } => inputs::handle(encrypt, day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
-                day,
+                days,
+                template,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+                let template = read_template(&template);
+                let mut failed = false;
+                for day in days.days() {
+                    // downloading first lets the module be titled after the puzzle.
+                    if download {
+                        download::handle(day);
+                    }
+                    failed |= !scaffold::handle(day, &template, overwrite);
+                }
+                if failed {
+                    process::exit(1);
                 }
+                println!("---");
+                println!("🎄 Type `cargo solve <day>` to run your solution.");
             }
+            AppArguments::Stars => stars::handle(),
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                allocations,
+                profile_cpu,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+            } => solve::handle(day, release, dhat, allocations, profile_cpu, submit, input),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
                         download::handle(day);
+                        scaffold::handle(day, &read_template(scaffold::DEFAULT_TEMPLATE), false);
                         read::handle(day)
                     }
                     None => {
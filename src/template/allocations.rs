@@ -0,0 +1,132 @@
+//! Heap allocation counting for the solution binaries.
+//!
+//! With the `allocations` feature, the `solution!` macro installs [`CountingAlloc`] as the global
+//! allocator of every day, unless the `dhat-heap` feature installs DHAT's instead. The runner then
+//! measures what each stage allocates while it runs.
+//!
+//! Counting costs a few atomic operations per allocation, which shows in the timings of stages
+//! that allocate a lot, so it is off unless asked for with `--allocations`. The allocator is
+//! recorded with stored timings, so they are not compared with timings measured on another one.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Name of the global allocator the `solution!` macro installs in this build.
+#[cfg(feature = "dhat-heap")]
+pub const GLOBAL_ALLOCATOR: &str = "dhat";
+#[cfg(all(feature = "allocations", not(feature = "dhat-heap")))]
+pub const GLOBAL_ALLOCATOR: &str = COUNTING;
+#[cfg(not(any(feature = "allocations", feature = "dhat-heap")))]
+pub const GLOBAL_ALLOCATOR: &str = SYSTEM;
+
+const COUNTING: &str = "counting";
+const SYSTEM: &str = "system";
+
+/// Name of the global allocator of solutions built with the `allocations` feature if `counting`,
+/// and without it otherwise.
+pub fn allocator_name(counting: bool) -> &'static str {
+    if counting { COUNTING } else { SYSTEM }
+}
+
+/// The system allocator, counting allocations and how many bytes are allocated at once.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            // growing a collection counts as an allocation, like it would without `realloc`.
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            if new_size > layout.size() {
+                grow(new_size - layout.size());
+            } else {
+                CURRENT_BYTES.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+fn grow(bytes: usize) {
+    let current = CURRENT_BYTES.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+}
+
+/// What a stage allocated while it ran.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub count: u64,
+    /// The most bytes the stage had allocated at once, on top of what was allocated before.
+    pub peak_bytes: u64,
+}
+
+/// Runs `func`, counting what it allocates. Returns no counts if [`CountingAlloc`] is not the
+/// global allocator.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    let baseline = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(baseline, Ordering::Relaxed);
+    let count = ALLOCATIONS.load(Ordering::Relaxed);
+
+    let result = func();
+
+    let allocations = (GLOBAL_ALLOCATOR == COUNTING).then(|| Allocations {
+        count: ALLOCATIONS.load(Ordering::Relaxed) - count,
+        peak_bytes: (PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(baseline)) as u64,
+    });
+    (result, allocations)
+}
+
+/// Formats a number of bytes in binary units, e.g. `512 B` or `4.2 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_bytes;
+
+    #[test]
+    fn formats_bytes_in_binary_units() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(4300), "4.2 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
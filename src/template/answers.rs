@@ -0,0 +1,307 @@
+use std::collections::{BTreeMap, HashMap};
+use std::{fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, data_dir, data_path};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Whether Advent of Code accepted an answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+}
+
+impl Verdict {
+    /// The verdict in the response of Advent of Code to a submission, if it judged the answer.
+    /// Answers can also be refused without a verdict, e.g. when submitting too soon.
+    pub fn from_response(response: &str) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if response.contains("That's not the right answer") {
+            Some(Verdict::Incorrect)
+        } else {
+            None
+        }
+    }
+}
+
+/// An answer Advent of Code has judged, from a submission or from a downloaded puzzle, which
+/// lists the accepted answers of solved parts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Every judged answer, stored in `data/answers.json` so progress is known offline.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Submission>,
+}
+
+impl Answers {
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(data_dir().join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Reads the stored answers. If not present, returns no answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(data_dir().join(ANSWERS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Records a judged answer, replacing an earlier verdict on the same answer.
+    pub fn record(&mut self, submission: Submission) {
+        self.data.retain(|s| {
+            (s.day, s.part, &s.answer) != (submission.day, submission.part, &submission.answer)
+        });
+        self.data.push(submission);
+        self.data.sort_by_key(|s| (s.day, s.part));
+    }
+
+    /// The verdict on `answer`, if it has been judged.
+    pub fn verdict(&self, day: Day, part: u8, answer: &str) -> Option<Verdict> {
+        self.data
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.answer == answer)
+            .map(|s| s.verdict)
+    }
+
+    /// Records the accepted answers listed in the puzzle description of `day`, as downloaded by
+    /// aoc-cli. Returns how many there are.
+    pub fn import_puzzle(&mut self, day: Day, puzzle: &str) -> usize {
+        let accepted = puzzle_answers(puzzle);
+        for (idx, answer) in accepted.iter().enumerate() {
+            self.record(Submission {
+                day,
+                part: idx as u8 + 1,
+                answer: answer.clone(),
+                verdict: Verdict::Correct,
+            });
+        }
+        accepted.len()
+    }
+
+    /// Like [`Answers::import_puzzle`], for the downloaded puzzle description of `day` if there
+    /// is one.
+    pub fn import_puzzle_file(&mut self, day: Day) -> usize {
+        fs::read_to_string(data_path("puzzles", &format!("{day}.md")))
+            .map_or(0, |puzzle| self.import_puzzle(day, &puzzle))
+    }
+
+    /// Which parts of each day have a star, for days with at least one.
+    pub fn stars(&self) -> BTreeMap<Day, [bool; 2]> {
+        let mut stars: BTreeMap<Day, [bool; 2]> = BTreeMap::new();
+        for s in &self.data {
+            if s.verdict == Verdict::Correct && (1..=2).contains(&s.part) {
+                stars.entry(s.day).or_default()[usize::from(s.part - 1)] = true;
+            }
+        }
+        stars
+    }
+}
+
+/// Answers of the solved parts in a puzzle description, in order of the parts. aoc-cli renders
+/// them as ``Your puzzle answer was `42`.``
+fn puzzle_answers(puzzle: &str) -> Vec<String> {
+    puzzle
+        .split("Your puzzle answer was")
+        .skip(1)
+        .filter_map(|rest| {
+            let rest = rest.trim_start();
+            let answer = match rest.strip_prefix('`') {
+                Some(quoted) => quoted.split('`').next()?,
+                None => rest.split(['.', '\n']).next()?,
+            };
+            let answer = answer.trim();
+            (!answer.is_empty()).then(|| answer.to_string())
+        })
+        .take(2)
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Verdict {
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?
+            .clone();
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Submission, Verdict, puzzle_answers};
+    use crate::day;
+
+    fn submission(day: u8, part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            day: crate::template::Day::new(day).unwrap(),
+            part,
+            answer: answer.into(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn finds_answers_in_puzzles() {
+        let puzzle = "\\--- Day 1: Secret Entrance ---\n...\nYour puzzle answer was `1158`.\n\n\\--- Part Two ---\n...\nYour puzzle answer was `6860`.\n\nBoth parts of this puzzle are complete!";
+        assert_eq!(puzzle_answers(puzzle), ["1158", "6860"]);
+        assert_eq!(puzzle_answers("Your puzzle answer was 42.\n"), ["42"]);
+        assert!(puzzle_answers("\\--- Day 2: Gift Shop ---\n").is_empty());
+    }
+
+    #[test]
+    fn reads_verdicts_from_responses() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Some(Verdict::Incorrect)
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently."),
+            None
+        );
+    }
+
+    #[test]
+    fn counts_stars_of_correct_answers() {
+        let mut answers = Answers::default();
+        answers.record(submission(1, 1, "12", Verdict::Incorrect));
+        answers.record(submission(1, 1, "13", Verdict::Correct));
+        answers.record(submission(3, 2, "7", Verdict::Incorrect));
+        answers.record(submission(2, 2, "5", Verdict::Correct));
+        let stars: Vec<_> = answers.stars().into_iter().collect();
+        assert_eq!(stars, [(day!(1), [true, false]), (day!(2), [false, true])]);
+        assert_eq!(answers.verdict(day!(1), 1, "12"), Some(Verdict::Incorrect));
+        assert_eq!(answers.verdict(day!(1), 1, "14"), None);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut answers = Answers::default();
+        answers.record(submission(1, 1, "12", Verdict::Incorrect));
+        answers.record(submission(1, 2, "13", Verdict::Correct));
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Answers::try_from(json).unwrap().data, answers.data);
+    }
+}
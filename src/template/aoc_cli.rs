@@ -1,10 +1,12 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    io::{self, Write},
     process::{Command, Output, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::template::Day;
+use crate::template::{Day, data_path};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -77,24 +79,53 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // the response is captured to record whether the answer was right, so echo it.
+    let output = Command::new("aoc")
+        .args(&args)
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+    io::stdout().write_all(&output.stdout).ok();
+    io::stderr().write_all(&output.stderr).ok();
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    data_path("inputs", &format!("{day}.txt"))
+        .display()
+        .to_string()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    data_path("puzzles", &format!("{day}.md"))
+        .display()
+        .to_string()
 }
 
-fn get_year() -> Option<u16> {
+pub(crate) fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
     }
 }
 
+/// The `AOC_YEAR`, or the current year if it is not set.
+pub(crate) fn year() -> u16 {
+    get_year().unwrap_or_else(|| {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        // average length of a gregorian year, close enough outside of new year's eve.
+        1970 + (secs / 31_556_952) as u16
+    })
+}
+
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
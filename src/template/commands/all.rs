@@ -1,5 +1,14 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    DaySelection, all_days,
+    run_multi::{RunOptions, run_multi},
+};
+
+pub fn handle(selection: &DaySelection, options: RunOptions) {
+    let days = selection.resolve(all_days(), &options);
+    let summary = run_multi(&days, options);
+    if !summary.failures.is_empty() {
+        process::exit(1);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::template::commands::stars;
 use crate::template::{Day, aoc_cli};
 use std::process;
 
@@ -13,4 +14,7 @@ pub fn handle(day: Day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    // the puzzle lists the answers of parts already solved, e.g. on another machine.
+    stars::update(std::iter::once(day));
 }
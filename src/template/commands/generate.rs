@@ -0,0 +1,38 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, process};
+
+use crate::generators::generator;
+use crate::template::Day;
+use crate::util::rng::Rng;
+
+pub fn handle(day: Day, size: Option<usize>, seed: Option<u64>, out: Option<String>) {
+    let Some(generator) = generator(day) else {
+        eprintln!("Day {day} has no input generator, add one to src/generators.");
+        process::exit(1);
+    };
+    let size = size.unwrap_or(generator.default_size);
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64
+    });
+
+    let input = (generator.generate)(&mut Rng::new(seed), size);
+
+    match out {
+        None => print!("{input}"),
+        Some(path) => match fs::write(&path, input) {
+            Ok(()) => {
+                println!(
+                    "Generated input with {size} {} (seed {seed}) to \"{path}\"",
+                    generator.unit
+                );
+            }
+            Err(e) => {
+                eprintln!("Failed to write generated input: {e}");
+                process::exit(1);
+            }
+        },
+    }
+}
@@ -0,0 +1,57 @@
+use std::{fs, process};
+
+use crate::template::encryption::{InputKey, encrypted_path};
+use crate::template::{Day, all_days, data_path};
+
+/// Encrypts the inputs of `day`, or of every day, to `NN.txt.enc`, or decrypts them back.
+pub fn handle(encrypt: bool, day: Option<Day>) {
+    let key = match InputKey::from_env() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{e} Set it to a passphrase shared with your team to encrypt inputs.");
+            process::exit(1);
+        }
+    };
+
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+    let mut failed = false;
+    for day in days {
+        let result = if encrypt {
+            encrypt_input(&key, day)
+        } else {
+            decrypt_input(&key, day)
+        };
+        if let Err(e) = result {
+            eprintln!("Day {day}: {e}");
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn encrypt_input(key: &InputKey, day: Day) -> Result<(), String> {
+    let path = data_path("inputs", &format!("{day}.txt"));
+    let encrypted = encrypted_path(&path);
+    match fs::read(&path) {
+        Ok(input) if !input.is_empty() => {
+            fs::write(&encrypted, key.encrypt(&input)).map_err(|e| e.to_string())?;
+            println!("Encrypted input to \"{}\"", encrypted.display());
+        }
+        // scaffolded days have an empty input until it is downloaded.
+        _ => {}
+    }
+    Ok(())
+}
+
+fn decrypt_input(key: &InputKey, day: Day) -> Result<(), String> {
+    let path = data_path("inputs", &format!("{day}.txt"));
+    let Ok(encrypted) = fs::read(encrypted_path(&path)) else {
+        return Ok(());
+    };
+    let input = key.decrypt(&encrypted).map_err(|e| e.to_string())?;
+    fs::write(&path, input).map_err(|e| e.to_string())?;
+    println!("Decrypted input to \"{}\"", path.display());
+    Ok(())
+}
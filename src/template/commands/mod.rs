@@ -1,7 +1,10 @@
 pub mod all;
 pub mod download;
+pub mod generate;
+pub mod inputs;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
 pub mod time;
 pub mod tui;
@@ -1,79 +1,143 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
 };
 
-use crate::template::Day;
+use crate::template::{Day, aoc_cli, data_path, project_dir};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Template used when none is selected with `--template`.
+pub const DEFAULT_TEMPLATE: &str = "tui";
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
+const EXAMPLE_CASES_TEMPLATE: &str = "\
+# Expected answers for the example, `cargo test` checks every part that has one.
+[[case]]
+# part_one = 0
+# part_two = 0
+";
+
+/// What happened to one of the files of a scaffolded day.
+enum FileOutcome {
+    Created,
+    Overwritten,
+    Skipped,
+    Failed(io::Error),
+}
+
+/// Writes `contents` to `path`, leaving an existing file alone unless `overwrite` is set.
+fn write_file(path: &Path, contents: &str, overwrite: bool) -> FileOutcome {
+    let existed = path.exists();
+    let mut options = OpenOptions::new();
     if overwrite {
-        file.create(true);
+        options.create(true).truncate(true);
     } else {
-        file.create_new(true);
+        options.create_new(true);
     }
-    file.truncate(true).write(true).open(path)
-}
-
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
+    match options
         .write(true)
-        .create(true)
-        .truncate(true)
         .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+    {
+        Ok(()) if existed => FileOutcome::Overwritten,
+        Ok(()) => FileOutcome::Created,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => FileOutcome::Skipped,
+        Err(e) => FileOutcome::Failed(e),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
-
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// Prints what happened to a file, returning whether it went wrong.
+fn report(kind: &str, path: &Path, outcome: FileOutcome) -> bool {
+    let path = path.strip_prefix(project_dir()).unwrap_or(path).display();
+    match outcome {
+        FileOutcome::Created => println!("Created {kind} \"{path}\""),
+        FileOutcome::Overwritten => println!("Overwrote {kind} \"{path}\""),
+        FileOutcome::Skipped => println!("Skipped {kind} \"{path}\", it already exists"),
+        FileOutcome::Failed(e) => {
+            eprintln!("Failed to create {kind} \"{path}\": {e}");
+            return true;
         }
+    }
+    false
+}
+
+/// Reads `templates/{name}.txt`. The error lists the templates there are.
+pub fn read_template(name: &str) -> Result<String, String> {
+    let dir = project_dir().join("templates");
+    fs::read_to_string(dir.join(format!("{name}.txt"))).map_err(|e| {
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let name = path.file_name()?.to_str()?.strip_suffix(".txt")?;
+                Some(name.to_string())
+            })
+            .collect();
+        names.sort();
+        format!(
+            "could not read template \"{name}\": {e}. Available templates: {}.",
+            names.join(", ")
+        )
+    })
+}
+
+/// Title of the puzzle of `day`, e.g. `Day 1: Secret Entrance` if its description has been
+/// downloaded, or `Day 1` otherwise.
+fn puzzle_title(day: Day) -> String {
+    let fallback = format!("Day {}", day.into_inner());
+    let Ok(puzzle) = fs::read_to_string(data_path("puzzles", &format!("{day}.md"))) else {
+        return fallback;
     };
+    puzzle
+        .lines()
+        .find(|line| line.contains("--- Day "))
+        .map(|line| line.trim_matches(|c: char| "#-\\".contains(c) || c.is_whitespace()))
+        .map_or(fallback, str::to_string)
+}
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+/// Creates the module of `day` from the `template` source, and its input and example files.
+/// Existing inputs and examples are never overwritten, the module and example cases only with
+/// `overwrite`. Returns whether every file was created or skipped.
+pub fn handle(day: Day, template: &str, overwrite: bool) -> bool {
+    let module = template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace("%YEAR%", &aoc_cli::year().to_string())
+        .replace("%TITLE%", &puzzle_title(day));
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
-    }
+    let files = [
+        (
+            "module file",
+            project_dir()
+                .join("src")
+                .join("bin")
+                .join(format!("{day}.rs")),
+            module.as_str(),
+            overwrite,
+        ),
+        (
+            "empty input file",
+            data_path("inputs", &format!("{day}.txt")),
+            "",
+            false,
+        ),
+        (
+            "empty example file",
+            data_path("examples", &format!("{day}.txt")),
+            "",
+            false,
+        ),
+        (
+            "example cases file",
+            data_path("examples", &format!("{day}.toml")),
+            EXAMPLE_CASES_TEMPLATE,
+            overwrite,
+        ),
+    ];
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    let mut failed = false;
+    for (kind, path, contents, overwrite) in files {
+        failed |= report(kind, &path, write_file(&path, contents, overwrite));
     }
-
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    !failed
 }
@@ -1,8 +1,21 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{Day, project_dir};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    allocations: bool,
+    profile_cpu: bool,
+    submit_part: Option<u8>,
+    input: Option<String>,
+) {
+    if profile_cpu && !cfg!(unix) {
+        eprintln!("CPU profiling is only supported on unix systems.");
+        process::exit(1);
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,19 +25,41 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
+    } else if profile_cpu {
+        // the `bench` profile keeps the debug info that names the frames of the flamegraph.
+        cmd_args.extend([
+            "--profile".to_string(),
+            "bench".to_string(),
+            "--features".to_string(),
+            "cpu-profile".to_string(),
+        ]);
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    if allocations {
+        cmd_args.extend(["--features".to_string(), "allocations".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
+    if profile_cpu {
+        cmd_args.push("--profile-cpu".to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .current_dir(project_dir())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -0,0 +1,40 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::{Day, all_days, readme_stars};
+
+/// Records the answers of every downloaded puzzle and updates the ⭐️ table in the readme.
+pub fn handle() {
+    if !update(all_days()) {
+        process::exit(1);
+    }
+}
+
+/// Records the answers listed in the downloaded puzzles of `days` and updates the ⭐️ table in
+/// the readme. Returns whether both succeeded.
+pub fn update(days: impl Iterator<Item = Day>) -> bool {
+    let mut answers = Answers::read_from_file();
+    for day in days {
+        answers.import_puzzle_file(day);
+    }
+
+    if let Err(e) = answers.store_file() {
+        eprintln!("Failed to store answers: {e}");
+        return false;
+    }
+    match readme_stars::update(&answers) {
+        Ok(()) => {
+            let stars: usize = answers
+                .stars()
+                .values()
+                .map(|parts| parts.iter().filter(|solved| **solved).count())
+                .sum();
+            println!("🎄 Updated the readme, you have {stars} ⭐️.");
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to update the readme: {e:?}");
+            false
+        }
+    }
+}
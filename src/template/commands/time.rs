@@ -1,40 +1,109 @@
-use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::template::run_multi::run_multi;
+use crate::template::environment::comparable;
+use crate::template::export::{self, ExportFormat};
+use crate::template::run_multi::{RunOptions, run_multi};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{DaySelection, all_days, project_dir, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    selection: &DaySelection,
+    run_all: bool,
+    store: bool,
+    options: RunOptions,
+    export: Option<ExportFormat>,
+    out: Option<String>,
+) {
+    // days are timed one at a time, so they do not compete for the CPU.
+    let options = RunOptions {
+        profile: Some(options.profile.unwrap_or("release")),
+        is_timed: true,
+        jobs: 1,
+        ..options
+    };
+    let environment = options.environment();
     let stored_timings = Timings::read_from_file();
+    // only timings from the same machine and build are compared with this run.
+    let comparable_timings = stored_timings.for_environment(Some(&environment));
+    let ignored = stored_timings.data.len() - comparable_timings.data.len();
+    if ignored > 0 {
+        println!(
+            "Ignoring {ignored} stored timing(s) measured on another machine or build, this run is on {}.\n",
+            environment.describe()
+        );
+    }
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
+    // without `--all` or a filter, days that are fully benched are skipped.
+    let days_to_run = selection.resolve(
+        all_days().filter(|day| {
+            run_all || selection.has_filters() || !comparable_timings.is_day_complete(*day)
+        }),
+        &options,
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(&days_to_run, options).timings.unwrap();
+    for timing in &mut timings.data {
+        timing.environment = Some(environment.clone());
+
+        // keep the stored timing of the part that did not run.
+        let stored = comparable_timings.data.iter().find(|t| t.day == timing.day);
+        if let (Some(part), Some(stored)) = (options.part, stored) {
+            let other = 3 - part;
+            if other == 1 {
+                timing.part_1 = stored.part_1;
+            } else {
+                timing.part_2 = stored.part_2;
+            }
+            timing.status[usize::from(other - 1)] = stored.status[usize::from(other - 1)];
+            timing.total_nanos += stored.part(other).map_or(0, |stats| stats.average);
+        }
+    }
+    let merged_timings = stored_timings.merge(&timings);
+    let current_timings = merged_timings.for_environment(Some(&environment));
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        if let Err(e) = timings.append_history(recorded_at) {
+            eprintln!("Failed to record benchmark history: {e}");
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(current_timings.clone()) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e:?}");
             }
         }
     }
+
+    if let Some(format) = export {
+        let path = out.map_or_else(
+            || {
+                project_dir()
+                    .join("target")
+                    .join(format!("benchmarks.{}", format.extension()))
+            },
+            PathBuf::from,
+        );
+        let history: Vec<_> = Timings::read_history()
+            .into_iter()
+            .filter(|r| comparable(r.timing.environment.as_ref(), Some(&environment)))
+            .collect();
+        let contents = export::render(format, &current_timings, &history);
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, contents));
+        match result {
+            Ok(()) => println!("Exported benchmarks to \"{}\".", path.display()),
+            Err(e) => eprintln!("Failed to export benchmarks: {e}"),
+        }
+    }
 }
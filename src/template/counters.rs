@@ -0,0 +1,244 @@
+//! Hardware performance counters of benched stages, for `cargo time --counters`.
+//!
+//! On Linux, the runner counts what a stage does with `perf_event_open(2)` in a loop after it was
+//! benched. Only the thread running the stage is counted, work a stage hands to other threads
+//! (e.g. with `rayon`) is not. The counters are not available on other systems, in many virtual
+//! machines, or if `/proc/sys/kernel/perf_event_paranoid` is above `2`.
+
+/// Average counts of one run of a stage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counters {
+    pub instructions: u64,
+    pub cycles: u64,
+    pub branch_misses: u64,
+    pub cache_misses: u64,
+}
+
+impl Counters {
+    /// Instructions per cycle.
+    pub fn ipc(&self) -> Option<f64> {
+        #[allow(clippy::cast_precision_loss)]
+        (self.cycles > 0).then(|| self.instructions as f64 / self.cycles as f64)
+    }
+
+    /// e.g. `1.2M instructions, 400.0K cycles, IPC 3.00, 1.0K branch misses, 12 cache misses`.
+    pub fn describe(&self) -> String {
+        let ipc = self.ipc().map_or("-".into(), |ipc| format!("{ipc:.2}"));
+        format!(
+            "{} instructions, {} cycles, IPC {ipc}, {} branch misses, {} cache misses",
+            format_count(self.instructions),
+            format_count(self.cycles),
+            format_count(self.branch_misses),
+            format_count(self.cache_misses)
+        )
+    }
+}
+
+/// Formats a count with a metric suffix, e.g. `999`, `1.2K` or `3.4M`.
+fn format_count(count: u64) -> String {
+    const SUFFIXES: [&str; 4] = ["K", "M", "G", "T"];
+    if count < 1000 {
+        return count.to_string();
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = count as f64 / 1000.0;
+    let mut suffix = 0;
+    while value >= 1000.0 && suffix < SUFFIXES.len() - 1 {
+        value /= 1000.0;
+        suffix += 1;
+    }
+    format!("{value:.1}{}", SUFFIXES[suffix])
+}
+
+/// Counts what `func` does on the calling thread over `runs` runs, returning the average of a
+/// run.
+#[cfg(target_os = "linux")]
+pub fn count(runs: u64, mut func: impl FnMut()) -> Result<Counters, String> {
+    let group = perf::Group::open().map_err(|e| format!("could not open counters: {e}"))?;
+    group.enable().map_err(|e| e.to_string())?;
+    for _ in 0..runs {
+        func();
+    }
+    group.disable().map_err(|e| e.to_string())?;
+
+    let [instructions, cycles, branch_misses, cache_misses] =
+        group.read().map_err(|e| e.to_string())?;
+    let runs = runs.max(1);
+    Ok(Counters {
+        instructions: instructions / runs,
+        cycles: cycles / runs,
+        branch_misses: branch_misses / runs,
+        cache_misses: cache_misses / runs,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn count(_runs: u64, _func: impl FnMut()) -> Result<Counters, String> {
+    Err("hardware counters are only available on Linux".into())
+}
+
+/// A minimal binding of `perf_event_open(2)`, see its man page for the constants.
+#[cfg(target_os = "linux")]
+mod perf {
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    /// Instructions, cycles, branch misses and cache misses, in the order they are read.
+    const EVENTS: [u64; 4] = [1, 0, 5, 3];
+
+    const FORMAT_TOTAL_TIME_ENABLED: u64 = 1 << 0;
+    const FORMAT_TOTAL_TIME_RUNNING: u64 = 1 << 1;
+    const FORMAT_GROUP: u64 = 1 << 3;
+
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+    const PERF_IOC_FLAG_GROUP: libc::c_int = 1;
+
+    // `_IO('$', n)`, which sets a direction bit on these architectures.
+    const IOC_NONE: libc::Ioctl = if cfg!(any(
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "sparc64"
+    )) {
+        1 << 29
+    } else {
+        0
+    };
+    const IOC_ENABLE: libc::Ioctl = IOC_NONE | 0x2400;
+    const IOC_DISABLE: libc::Ioctl = IOC_NONE | 0x2401;
+    const IOC_RESET: libc::Ioctl = IOC_NONE | 0x2403;
+
+    /// The first version of `struct perf_event_attr`, which every kernel accepts.
+    #[repr(C)]
+    #[derive(Default)]
+    struct Attr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    /// Counters that are enabled, disabled and read together, led by the first one.
+    pub struct Group {
+        leader: File,
+        _members: Vec<OwnedFd>,
+    }
+
+    impl Group {
+        pub fn open() -> io::Result<Self> {
+            let leader = open(EVENTS[0], None)?;
+            let members = EVENTS[1..]
+                .iter()
+                .map(|event| open(*event, Some(&leader)))
+                .collect::<io::Result<_>>()?;
+            Ok(Group {
+                leader: File::from(leader),
+                _members: members,
+            })
+        }
+
+        pub fn enable(&self) -> io::Result<()> {
+            self.ioctl(IOC_RESET)?;
+            self.ioctl(IOC_ENABLE)
+        }
+
+        pub fn disable(&self) -> io::Result<()> {
+            self.ioctl(IOC_DISABLE)
+        }
+
+        /// Reads the counts, in the order of [`EVENTS`].
+        pub fn read(&self) -> io::Result<[u64; 4]> {
+            // the number of counters, the time enabled and running, then the counts.
+            let mut buf = [0_u8; 8 * (3 + EVENTS.len())];
+            (&self.leader).read_exact(&mut buf)?;
+            let values: Vec<u64> = buf
+                .chunks_exact(8)
+                .map(|chunk| u64::from_ne_bytes(chunk.try_into().unwrap()))
+                .collect();
+            if values[2] == 0 {
+                return Err(io::Error::other(
+                    "the counters could not be scheduled on the CPU",
+                ));
+            }
+            Ok([values[3], values[4], values[5], values[6]])
+        }
+
+        fn ioctl(&self, request: libc::Ioctl) -> io::Result<()> {
+            // SAFETY: the perf ioctls take an integer argument and the fd is open.
+            let result =
+                unsafe { libc::ioctl(self.leader.as_raw_fd(), request, PERF_IOC_FLAG_GROUP) };
+            if result == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn open(event: u64, leader: Option<&OwnedFd>) -> io::Result<OwnedFd> {
+        let mut attr = Attr {
+            kind: PERF_TYPE_HARDWARE,
+            size: u32::try_from(size_of::<Attr>()).unwrap(),
+            config: event,
+            read_format: FORMAT_GROUP | FORMAT_TOTAL_TIME_ENABLED | FORMAT_TOTAL_TIME_RUNNING,
+            flags: FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+            ..Attr::default()
+        };
+        if leader.is_none() {
+            // members follow their leader, which is enabled once everything is set up.
+            attr.flags |= FLAG_DISABLED;
+        }
+        let group_fd = leader.map_or(-1, AsRawFd::as_raw_fd);
+        // SAFETY: `attr` is a valid `perf_event_attr` of the size it declares.
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_perf_event_open,
+                &raw mut attr,
+                0,
+                -1,
+                group_fd,
+                PERF_FLAG_FD_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = libc::c_int::try_from(fd).map_err(io::Error::other)?;
+        // SAFETY: the fd was just opened and is owned by nothing else.
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Counters, format_count};
+
+    #[test]
+    fn describes_counters() {
+        let counters = Counters {
+            instructions: 1_200_000,
+            cycles: 400_000,
+            branch_misses: 1000,
+            cache_misses: 12,
+        };
+        assert_eq!(
+            counters.describe(),
+            "1.2M instructions, 400.0K cycles, IPC 3.00, 1.0K branch misses, 12 cache misses"
+        );
+        assert_eq!(Counters::default().ipc(), None);
+        assert_eq!(format_count(999), "999");
+    }
+}
@@ -0,0 +1,65 @@
+//! Sampling CPU profiles of solution parts for `cargo solve <day> --profile-cpu`.
+//!
+//! The solution is built with the `bench` profile, which keeps debug info for symbol names, and
+//! the `cpu-profile` feature. Every part that produced an answer then runs in a loop while the
+//! process is sampled, and the samples are written as a flamegraph to
+//! `target/profiles/<day>-part<part>.svg`.
+use std::path::PathBuf;
+
+use crate::template::Day;
+
+/// Runs `func` in a loop while sampling the process and writes a flamegraph of the samples.
+/// Returns where it was written and how often `func` ran.
+#[cfg(all(feature = "cpu-profile", unix))]
+pub fn profile<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    part: u8,
+) -> Result<(PathBuf, u64), String> {
+    use std::fs::{self, File};
+    use std::hint::black_box;
+    use std::time::{Duration, Instant};
+
+    use crate::template::project_dir;
+
+    /// How long a part runs in a loop while it is sampled. Parts slower than this run once.
+    const DURATION: Duration = Duration::from_secs(5);
+    /// Samples per second, a prime so the samples do not line up with periodic work.
+    const FREQUENCY: i32 = 997;
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let timer = Instant::now();
+    let mut runs = 0;
+    while runs == 0 || timer.elapsed() < DURATION {
+        black_box(func(black_box(input)));
+        runs += 1;
+    }
+
+    let report = guard.report().build().map_err(|e| e.to_string())?;
+    let path = project_dir()
+        .join("target")
+        .join("profiles")
+        .join(format!("{day}-part{part}.svg"));
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let file = File::create(&path).map_err(|e| e.to_string())?;
+    report.flamegraph(file).map_err(|e| e.to_string())?;
+    Ok((path, runs))
+}
+
+#[cfg(not(all(feature = "cpu-profile", unix)))]
+pub fn profile<I: Copy, T>(
+    _func: impl Fn(I) -> T,
+    _input: I,
+    _day: Day,
+    _part: u8,
+) -> Result<(PathBuf, u64), String> {
+    Err("CPU profiling needs the `cpu-profile` feature on a unix system.".into())
+}
@@ -94,6 +94,81 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+/// An inclusive range of days, parsed from a single day (`5`) or from `1..12`, which like
+/// `1..=12` and `1-12` includes the last day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayRange {
+    first: Day,
+    last: Day,
+}
+
+impl DayRange {
+    /// The days in the range, in order.
+    pub fn days(self) -> impl Iterator<Item = Day> {
+        (self.first.0..=self.last.0).map(Day)
+    }
+}
+
+impl From<Day> for DayRange {
+    fn from(day: Day) -> Self {
+        Self {
+            first: day,
+            last: day,
+        }
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = DayFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((first, last)) = s.split_once("..").or_else(|| s.split_once('-')) else {
+            return s.parse::<Day>().map(Self::from);
+        };
+        let first: Day = first.parse()?;
+        let last: Day = last.strip_prefix('=').unwrap_or(last).parse()?;
+        if first > last {
+            return Err(DayFromStrError);
+        }
+        Ok(Self { first, last })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of days, parsed from a comma separated list of days and [`DayRange`]s, e.g. `3,5,8-12`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet {
+    ranges: Vec<DayRange>,
+}
+
+impl DaySet {
+    /// The days in the set, in order and without duplicates.
+    pub fn days(&self) -> impl Iterator<Item = Day> {
+        all_days().filter(|day| self.contains(*day))
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.ranges
+            .iter()
+            .any(|range| range.first <= day && day <= range.last)
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DayFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ranges = s
+            .split(',')
+            .map(|range| range.trim().parse())
+            .collect::<Result<_, _>>()?;
+        Ok(Self { ranges })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
@@ -143,7 +218,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{Day, all_days};
+    use super::{Day, DayRange, DaySet, all_days};
 
     #[test]
     fn all_days_iterator() {
@@ -176,6 +251,34 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_ranges() {
+        let days = |s: &str| {
+            s.parse::<DayRange>()
+                .map(|range| range.days().map(Day::into_inner).collect::<Vec<_>>())
+        };
+        assert_eq!(days("5").unwrap(), [5]);
+        assert_eq!(days("1..3").unwrap(), [1, 2, 3]);
+        assert_eq!(days("1..12").unwrap().len(), 12);
+        assert_eq!(days("24..=25").unwrap(), [24, 25]);
+        assert!(days("3..1").is_err());
+        assert!(days("0..3").is_err());
+        assert_eq!(days("8-10").unwrap(), [8, 9, 10]);
+        assert!(days("1..").is_err());
+    }
+
+    #[test]
+    fn parses_day_sets() {
+        let days = |s: &str| {
+            s.parse::<DaySet>()
+                .map(|set| set.days().map(Day::into_inner).collect::<Vec<_>>())
+        };
+        assert_eq!(days("3,5,8-12").unwrap(), [3, 5, 8, 9, 10, 11, 12]);
+        assert_eq!(days("12, 1..2, 2").unwrap(), [1, 2, 12]);
+        assert!(days("3,").is_err());
+        assert!(days("3,26").is_err());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
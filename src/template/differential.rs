@@ -0,0 +1,306 @@
+use std::fmt::{Debug, Write};
+use std::ops::RangeInclusive;
+use std::{env, fs};
+
+use crate::template::encryption::read_input_file;
+use crate::template::{Day, data_path, normalize_input, read_example_cases, runner::catch_panic};
+use crate::util::rng::Rng;
+
+/// Checks that alternative implementations of a part agree, on the examples, on the real input
+/// and on randomly generated inputs. Disagreements on generated inputs are shrunk to a small
+/// failing input before they are reported.
+///
+/// ```ignore
+/// Differential::new("part two")
+///     .with("beams", part_two)
+///     .with("memoized", part_two_memoized)
+///     .assert_agree_on_examples(DAY)
+///     .assert_agree_on_generated(200, generate_manifold);
+/// ```
+pub struct Differential<'a, T> {
+    label: &'a str,
+    implementations: Vec<(&'a str, Implementation<'a, T>)>,
+    shrink: Shrink<'a>,
+}
+
+type Implementation<'a, T> = Box<dyn Fn(&str) -> T + 'a>;
+type Shrink<'a> = Box<dyn Fn(&str) -> Vec<String> + 'a>;
+
+/// What every implementation returned for one input, or the message it panicked with.
+type Results<'a, T> = Vec<(&'a str, Result<T, String>)>;
+
+impl<'a, T: PartialEq + Debug> Differential<'a, T> {
+    pub fn new(label: &'a str) -> Self {
+        Self {
+            label,
+            implementations: vec![],
+            shrink: Box::new(remove_lines),
+        }
+    }
+
+    /// Registers an implementation. The first one is the reference the others are compared to.
+    #[must_use]
+    pub fn with(mut self, name: &'a str, implementation: impl Fn(&str) -> T + 'a) -> Self {
+        self.implementations.push((name, Box::new(implementation)));
+        self
+    }
+
+    /// Replaces how failing inputs are shrunk. `shrink` returns smaller or simpler variants of an
+    /// input, the default removes runs of lines.
+    #[must_use]
+    pub fn shrink_with(mut self, shrink: impl Fn(&str) -> Vec<String> + 'a) -> Self {
+        self.shrink = Box::new(shrink);
+        self
+    }
+
+    /// Runs every implementation on `input`, catching panics.
+    pub fn run(&self, input: &str) -> Results<'a, T> {
+        self.implementations
+            .iter()
+            .map(|(name, implementation)| (*name, catch_panic(|| implementation(input))))
+            .collect()
+    }
+
+    /// # Panics
+    /// Panics with a report if the implementations disagree on `input`.
+    pub fn assert_agree(&self, input: &str, source: &str) -> &Self {
+        let results = self.run(input);
+        if disagree(&results) {
+            panic!("{}", self.report(source, input, &results));
+        }
+        self
+    }
+
+    /// Checks every example input of `day`, see [`read_example_cases`].
+    pub fn assert_agree_on_examples(&self, day: Day) -> &Self {
+        let mut inputs: Vec<String> = read_example_cases(day)
+            .into_iter()
+            .map(|case| case.input)
+            .collect();
+        if inputs.is_empty() {
+            inputs.push(format!("{day}.txt"));
+        }
+        inputs.dedup();
+        for name in inputs {
+            let path = data_path("examples", &name);
+            let input = fs::read_to_string(&path)
+                .map(|input| normalize_input(&input))
+                .unwrap_or_else(|err| panic!("could not open {}: {err}", path.display()));
+            self.assert_agree(&input, &path.display().to_string());
+        }
+        self
+    }
+
+    /// Checks the real input of `day`, if it has been downloaded or can be decrypted.
+    pub fn assert_agree_on_input(&self, day: Day) -> &Self {
+        let path = data_path("inputs", &format!("{day}.txt"));
+        if let Ok(input) = read_input_file(&path)
+            && !input.is_empty()
+        {
+            self.assert_agree(&normalize_input(&input), &path.display().to_string());
+        }
+        self
+    }
+
+    /// Checks `cases` inputs made by `generate`. The seed is taken from the `AOC_SEED`
+    /// environment variable if set, so a failure can be replayed.
+    ///
+    /// # Panics
+    /// Panics on the first disagreement, reporting the input shrunk as far as it still fails.
+    pub fn assert_agree_on_generated(
+        &self,
+        cases: usize,
+        generate: impl Fn(&mut Rng) -> String,
+    ) -> &Self {
+        let seed = env::var("AOC_SEED")
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or(0x5eed_a0c2_0251_2025);
+        let mut rng = Rng::new(seed);
+        for case in 0..cases {
+            let input = generate(&mut rng);
+            let results = self.run(&input);
+            if !disagree(&results) {
+                continue;
+            }
+            // inputs where every implementation answers are only shrunk to inputs where they
+            // still all answer, so a panic on a malformed variant is not reported instead.
+            let answered = results.iter().all(|(_, result)| result.is_ok());
+            let (input, results) = self.minimize(input, results, answered);
+            let source = format!("generated input {case} with AOC_SEED={seed}, shrunk");
+            panic!("{}", self.report(&source, &input, &results));
+        }
+        self
+    }
+
+    /// Checks `cases` inputs made by the `generate` function of a day in
+    /// [`generators`](crate::generators), with a size picked from `sizes` for each input.
+    pub fn assert_agree_on_sizes(
+        &self,
+        cases: usize,
+        generate: fn(&mut Rng, usize) -> String,
+        sizes: RangeInclusive<u64>,
+    ) -> &Self {
+        self.assert_agree_on_generated(cases, |rng| {
+            let size = rng.range(sizes.clone()) as usize;
+            generate(rng, size)
+        })
+    }
+
+    /// Greedily applies the first shrink step that still disagrees, until none does.
+    fn minimize(
+        &self,
+        mut input: String,
+        mut results: Results<'a, T>,
+        answered: bool,
+    ) -> (String, Results<'a, T>) {
+        'shrink: loop {
+            for candidate in (self.shrink)(&input) {
+                let candidate_results = self.run(&candidate);
+                if disagree(&candidate_results)
+                    && (!answered || candidate_results.iter().all(|(_, r)| r.is_ok()))
+                {
+                    input = candidate;
+                    results = candidate_results;
+                    continue 'shrink;
+                }
+            }
+            return (input, results);
+        }
+    }
+
+    fn report(&self, source: &str, input: &str, results: &Results<'a, T>) -> String {
+        let mut report = format!(
+            "implementations of {} disagree on {source}:\n{input}",
+            self.label
+        );
+        if !input.ends_with('\n') {
+            report.push('\n');
+        }
+        for (name, result) in results {
+            match result {
+                Ok(answer) => writeln!(report, "  {name}: {answer:?}"),
+                Err(message) => writeln!(report, "  {name}: {message}"),
+            }
+            .unwrap();
+        }
+        report
+    }
+}
+
+/// Whether the results differ. Implementations that all panic agree that the input is invalid.
+fn disagree<T: PartialEq>(results: &Results<'_, T>) -> bool {
+    results
+        .windows(2)
+        .any(|pair| match (&pair[0].1, &pair[1].1) {
+            (Ok(a), Ok(b)) => a != b,
+            (Err(_), Err(_)) => false,
+            _ => true,
+        })
+}
+
+/// Variants of `input` with a run of lines removed, longest runs first.
+pub fn remove_lines(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut candidates = vec![];
+    let mut run = lines.len() / 2;
+    while run > 0 {
+        for start in (0..lines.len()).step_by(run) {
+            let end = (start + run).min(lines.len());
+            let mut candidate: String = lines[..start]
+                .iter()
+                .chain(&lines[end..])
+                .flat_map(|line| [line, "\n"])
+                .collect();
+            if !input.ends_with('\n') {
+                candidate.pop();
+            }
+            candidates.push(candidate);
+        }
+        run /= 2;
+    }
+    candidates
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Differential, remove_lines};
+    use std::panic::{self, AssertUnwindSafe};
+
+    fn sum(input: &str) -> i64 {
+        input.lines().map(|line| line.parse::<i64>().unwrap()).sum()
+    }
+
+    /// Wrong as soon as the input has a negative number.
+    fn sum_unsigned(input: &str) -> i64 {
+        input
+            .lines()
+            .map(|line| line.parse::<u32>().map_or(0, i64::from))
+            .sum()
+    }
+
+    #[test]
+    fn removes_runs_of_lines() {
+        assert_eq!(
+            remove_lines("a\nb\nc\nd\n"),
+            [
+                "c\nd\n",
+                "a\nb\n",
+                "b\nc\nd\n",
+                "a\nc\nd\n",
+                "a\nb\nd\n",
+                "a\nb\nc\n"
+            ]
+        );
+    }
+
+    #[test]
+    fn agreeing_implementations_pass() {
+        Differential::new("sum")
+            .with("fold", sum)
+            .with("loop", |input: &str| {
+                let mut total = 0;
+                for line in input.lines() {
+                    total += line.parse::<i64>().unwrap();
+                }
+                total
+            })
+            .assert_agree("1\n2\n3\n", "a small input")
+            .assert_agree_on_generated(50, |rng| {
+                (0..rng.below(20))
+                    .map(|_| format!("{}\n", rng.below(1000) as i64 - 500))
+                    .collect()
+            });
+    }
+
+    #[test]
+    fn reports_shrunk_disagreement() {
+        let differential = Differential::new("sum")
+            .with("signed", sum)
+            .with("unsigned", sum_unsigned);
+        let report = panic::catch_unwind(AssertUnwindSafe(|| {
+            differential.assert_agree_on_generated(50, |rng| {
+                (0..10)
+                    .map(|_| format!("{}\n", rng.below(1000) as i64 - 100))
+                    .collect()
+            });
+        }))
+        .unwrap_err();
+        let report = report.downcast_ref::<String>().unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+        // shrunk to the single negative number.
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with('-'));
+        assert_eq!(lines[3], "  unsigned: 0");
+    }
+
+    #[test]
+    fn all_panicking_is_agreement() {
+        Differential::new("parse")
+            .with("a", sum)
+            .with("b", sum)
+            .assert_agree("not a number", "an invalid input");
+    }
+}
@@ -0,0 +1,178 @@
+//! Encrypted puzzle inputs, so they can be shared through git without publishing them.
+//!
+//! An input `data/inputs/01.txt` is stored as `data/inputs/01.txt.enc`, encrypted with
+//! ChaCha20-Poly1305 under a key derived from the `AOC_INPUTS_KEY` environment variable.
+//! The nonce is derived from the key and the input, so encrypting the same input twice gives the
+//! same file and re-encrypting does not show up as a change in git.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce, aead::Aead};
+use sha2::{Digest, Sha256};
+
+/// Environment variable holding the passphrase the key is derived from.
+pub const KEY_VAR: &str = "AOC_INPUTS_KEY";
+
+const MAGIC: &[u8] = b"aoc-enc1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    MissingKey,
+    NotEncrypted,
+    WrongKeyOrCorrupted,
+}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::MissingKey => write!(f, "{KEY_VAR} is not set."),
+            EncryptionError::NotEncrypted => write!(f, "file is not an encrypted input."),
+            EncryptionError::WrongKeyOrCorrupted => {
+                write!(
+                    f,
+                    "could not decrypt, the key is wrong or the file is corrupted."
+                )
+            }
+        }
+    }
+}
+
+impl Error for EncryptionError {}
+
+/// Key to encrypt and decrypt inputs with.
+pub struct InputKey(Key);
+
+impl InputKey {
+    /// Derives the key from a passphrase. Use a long random one, e.g. `openssl rand -hex 32`, as
+    /// it is not stretched.
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Self(Sha256::digest(passphrase.as_bytes()))
+    }
+
+    /// Derives the key from the passphrase in [`KEY_VAR`].
+    pub fn from_env() -> Result<Self, EncryptionError> {
+        match env::var(KEY_VAR) {
+            Ok(passphrase) if !passphrase.is_empty() => Ok(Self::from_passphrase(&passphrase)),
+            _ => Err(EncryptionError::MissingKey),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let digest = Sha256::new()
+            .chain_update(self.0)
+            .chain_update(plaintext)
+            .finalize();
+        let nonce = Nonce::from_slice(&digest[..NONCE_LEN]);
+        let ciphertext = ChaCha20Poly1305::new(&self.0)
+            .encrypt(nonce, plaintext)
+            .expect("input too large to encrypt");
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let data = data
+            .strip_prefix(MAGIC)
+            .filter(|data| data.len() >= NONCE_LEN)
+            .ok_or(EncryptionError::NotEncrypted)?;
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        ChaCha20Poly1305::new(&self.0)
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| EncryptionError::WrongKeyOrCorrupted)
+    }
+}
+
+/// Path of the encrypted copy of the input at `path`, e.g. `01.txt.enc` for `01.txt`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+/// Reads the input at `path`, or decrypts its encrypted copy if the input is missing or empty.
+pub fn read_input_file(path: &Path) -> io::Result<String> {
+    read_input_file_with(path, InputKey::from_env)
+}
+
+fn read_input_file_with(
+    path: &Path,
+    key: impl FnOnce() -> Result<InputKey, EncryptionError>,
+) -> io::Result<String> {
+    let result = fs::read_to_string(path);
+    // `cargo scaffold` creates empty inputs, which must not hide an encrypted one from git.
+    let missing = match &result {
+        Ok(input) => input.is_empty(),
+        Err(err) => err.kind() == io::ErrorKind::NotFound,
+    };
+    if !missing {
+        return result;
+    }
+    let Ok(encrypted) = fs::read(encrypted_path(path)) else {
+        return result;
+    };
+    let plaintext = key()
+        .and_then(|key| key.decrypt(&encrypted))
+        .map_err(io::Error::other)?;
+    String::from_utf8(plaintext).map_err(io::Error::other)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{EncryptionError, InputKey, encrypted_path, read_input_file_with};
+    use std::{env, fs, path::Path, process};
+
+    #[test]
+    fn round_trips() {
+        let key = InputKey::from_passphrase("correct horse battery staple");
+        let encrypted = key.encrypt(b"L68\nR48\n");
+        assert_eq!(key.decrypt(&encrypted).unwrap(), b"L68\nR48\n");
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let key = InputKey::from_passphrase("correct horse battery staple");
+        assert_eq!(key.encrypt(b"L68\n"), key.encrypt(b"L68\n"));
+        assert_ne!(key.encrypt(b"L68\n"), key.encrypt(b"L69\n"));
+    }
+
+    #[test]
+    fn rejects_wrong_keys_and_plain_files() {
+        let encrypted = InputKey::from_passphrase("a").encrypt(b"L68\n");
+        assert!(matches!(
+            InputKey::from_passphrase("b").decrypt(&encrypted),
+            Err(EncryptionError::WrongKeyOrCorrupted)
+        ));
+        assert!(matches!(
+            InputKey::from_passphrase("a").decrypt(b"L68\n"),
+            Err(EncryptionError::NotEncrypted)
+        ));
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/01.txt")),
+            Path::new("data/inputs/01.txt.enc")
+        );
+    }
+
+    #[test]
+    fn decrypts_missing_or_empty_inputs() {
+        let dir = env::temp_dir().join(format!("aoc-encryption-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let key = || Ok(InputKey::from_passphrase("correct horse battery staple"));
+        let path = dir.join("01.txt");
+        fs::write(encrypted_path(&path), key().unwrap().encrypt(b"L68\n")).unwrap();
+
+        assert_eq!(read_input_file_with(&path, key).unwrap(), "L68\n");
+        fs::write(&path, "").unwrap();
+        assert_eq!(read_input_file_with(&path, key).unwrap(), "L68\n");
+        fs::write(&path, "R48\n").unwrap();
+        assert_eq!(read_input_file_with(&path, key).unwrap(), "R48\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,273 @@
+//! The machine and build a benchmark ran on, stored with its timings.
+//!
+//! Timings are only comparable when they were measured on the same CPU with the same compiler,
+//! profile, target features and global allocator. The git commit is recorded too, but differs between comparable
+//! runs as that is what is being benchmarked.
+use std::{collections::HashMap, env, fs, process::Command, thread};
+
+use tinyjson::JsonValue;
+
+use crate::template::project_dir;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    pub cpu: String,
+    pub cores: usize,
+    /// Version of the compiler, e.g. `rustc 1.91.0 (f8297e351 2025-10-28)`.
+    pub rustc: String,
+    /// Cargo profile the solutions were built with, e.g. `release` or `max`.
+    pub profile: String,
+    /// Comma-separated target features the solutions were compiled for.
+    pub target_features: String,
+    /// Global allocator of the solutions, `counting` when their allocations were counted and
+    /// `system` otherwise, as well as for timings stored before allocations could be counted.
+    pub allocator: String,
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+}
+
+impl Environment {
+    /// Describes this machine and the build of the running binary, for solutions built with
+    /// `profile` that run on `allocator`. The days are built by the same cargo with the same
+    /// flags, so they share its compiler and target features.
+    pub fn current(profile: &str, allocator: &str) -> Self {
+        let commit = git(&["rev-parse", "--short", "HEAD"]);
+        let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+            .is_some_and(|status| !status.is_empty());
+        Environment {
+            cpu: cpu_model().unwrap_or_else(|| "unknown".into()),
+            cores: thread::available_parallelism().map_or(0, std::num::NonZero::get),
+            rustc: env!("AOC_RUSTC_VERSION").into(),
+            profile: profile.into(),
+            target_features: env!("AOC_TARGET_FEATURES").into(),
+            allocator: allocator.into(),
+            commit,
+            dirty,
+        }
+    }
+
+    /// Whether timings measured in `self` and `other` can be compared: the same machine and
+    /// build, at any commit.
+    pub fn is_comparable(&self, other: &Self) -> bool {
+        (
+            &self.cpu,
+            self.cores,
+            &self.rustc,
+            &self.profile,
+            &self.target_features,
+            &self.allocator,
+        ) == (
+            &other.cpu,
+            other.cores,
+            &other.rustc,
+            &other.profile,
+            &other.target_features,
+            &other.allocator,
+        )
+    }
+
+    /// One line summary, e.g. `AMD Ryzen 7 5800X (16 cores), rustc 1.91.0, release profile`.
+    pub fn describe(&self) -> String {
+        let rustc = self.rustc.split(" (").next().unwrap_or(&self.rustc);
+        let cores = if self.cores == 1 { "core" } else { "cores" };
+        format!(
+            "{} ({} {cores}), {rustc}, {} profile, {} allocator",
+            self.cpu, self.cores, self.profile, self.allocator
+        )
+    }
+
+    /// Every field that has to match for timings to be comparable, with a label.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("CPU", self.cpu.clone()),
+            ("Logical cores", self.cores.to_string()),
+            ("Compiler", self.rustc.clone()),
+            ("Profile", self.profile.clone()),
+            ("Target features", self.target_features.clone()),
+            ("Allocator", self.allocator.clone()),
+        ]
+    }
+
+    /// The commit, marked if there were uncommitted changes, e.g. `abc1234 (dirty)`.
+    pub fn revision(&self) -> String {
+        let commit = self.commit.as_deref().unwrap_or("unknown");
+        format!("{commit}{}", if self.dirty { " (dirty)" } else { "" })
+    }
+}
+
+/// Whether timings measured in `a` and `b` can be compared. Timings stored before environments
+/// were recorded are only comparable with each other.
+pub fn comparable(a: Option<&Environment>, b: Option<&Environment>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.is_comparable(b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    let model = match env::consts::OS {
+        "linux" => fs::read_to_string("/proc/cpuinfo").ok().and_then(|info| {
+            info.lines()
+                .find(|line| line.starts_with("model name") || line.starts_with("Model"))
+                .and_then(|line| line.split_once(':'))
+                .map(|(_, model)| model.to_string())
+        }),
+        "macos" => output("sysctl", &["-n", "machdep.cpu.brand_string"]),
+        "windows" => env::var("PROCESSOR_IDENTIFIER").ok(),
+        _ => None,
+    }?;
+    let model = model.trim();
+    (!model.is_empty()).then(|| model.to_string())
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let mut git_args = vec!["-C", project_dir().to_str()?];
+    git_args.extend(args);
+    output("git", &git_args)
+}
+
+/// Trimmed stdout of a command that exited successfully.
+fn output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "target_features".into(),
+            JsonValue::String(value.target_features.clone()),
+        );
+        map.insert(
+            "allocator".into(),
+            JsonValue::String(value.allocator.clone()),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected environment to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected environment.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .map(|cores| *cores as usize)
+            .ok_or("Expected environment.cores to be a number.")?;
+
+        Ok(Environment {
+            cpu: string("cpu")?,
+            cores,
+            rustc: string("rustc")?,
+            profile: string("profile")?,
+            target_features: string("target_features")?,
+            allocator: string("allocator").unwrap_or_else(|_| "system".into()),
+            commit: string("commit").ok(),
+            dirty: json
+                .get("dirty")
+                .and_then(|v| v.get::<bool>())
+                .copied()
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Environment, comparable};
+    use tinyjson::JsonValue;
+
+    fn laptop() -> Environment {
+        Environment {
+            cpu: "Apple M2".into(),
+            cores: 8,
+            rustc: "rustc 1.91.0 (f8297e351 2025-10-28)".into(),
+            profile: "release".into(),
+            target_features: "neon".into(),
+            allocator: "counting".into(),
+            commit: Some("abc1234".into()),
+            dirty: false,
+        }
+    }
+
+    #[test]
+    fn compares_machines_and_builds_at_any_commit() {
+        let other_commit = Environment {
+            commit: Some("def5678".into()),
+            dirty: true,
+            ..laptop()
+        };
+        let max_profile = Environment {
+            profile: "max".into(),
+            ..laptop()
+        };
+        assert!(laptop().is_comparable(&other_commit));
+        assert!(!laptop().is_comparable(&max_profile));
+        let system_allocator = Environment {
+            allocator: "system".into(),
+            ..laptop()
+        };
+        assert!(!laptop().is_comparable(&system_allocator));
+        assert!(comparable(None, None));
+        assert!(!comparable(Some(&laptop()), None));
+        assert_eq!(
+            laptop().describe(),
+            "Apple M2 (8 cores), rustc 1.91.0, release profile, counting allocator"
+        );
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let json = JsonValue::from(&laptop());
+        assert_eq!(Environment::try_from(&json).unwrap(), laptop());
+    }
+
+    #[test]
+    fn reads_environments_stored_before_the_allocator() {
+        let mut json = JsonValue::from(&laptop());
+        if let JsonValue::Object(map) = &mut json {
+            map.remove("allocator");
+        }
+        let environment = Environment::try_from(&json).unwrap();
+        assert_eq!(environment.allocator, "system");
+        assert!(!environment.is_comparable(&laptop()));
+    }
+}
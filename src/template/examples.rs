@@ -0,0 +1,201 @@
+use std::fs;
+
+use crate::generators::generator;
+use crate::template::{
+    Answer, Day, Outcome, Params, Solution, data_path, normalize_input, params::parse_tables,
+    runner::catch_panic,
+};
+use crate::util::rng::Rng;
+
+/// An example input with the answers it is expected to produce, declared as a `[[case]]` table
+/// in `data/examples/NN.toml`:
+///
+/// ```toml
+/// to_connect = 10
+///
+/// [[case]]
+/// part_one = 40
+/// part_two = 25272
+///
+/// [[case]]
+/// name = "single_pair"
+/// input = "08-2.txt"
+/// to_connect = 1
+/// part_one = 2
+/// ```
+///
+/// `input` defaults to `NN.txt`, `name` to `example` (or `example_N` if there are several cases),
+/// and parts without an expected answer are not run. All other keys are [`Params`], which
+/// override the top-level ones. The [`solution!`](crate::solution) macro generates one test per
+/// case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleCase {
+    pub name: String,
+    /// File name of the input, relative to `data/examples`.
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+    pub params: Params,
+}
+
+impl ExampleCase {
+    fn new(day: Day, idx: usize, count: usize, mut params: Params, defaults: &Params) -> Self {
+        let name = params
+            .take("name")
+            .unwrap_or_else(|| default_name(idx, count));
+        let input = params.take("input").unwrap_or_else(|| format!("{day}.txt"));
+        let part_one = params.take("part_one");
+        let part_two = params.take("part_two");
+        let mut merged = defaults.clone();
+        merged.extend(params);
+        Self {
+            name,
+            input,
+            part_one,
+            part_two,
+            params: merged,
+        }
+    }
+}
+
+/// Name of the case at `idx` if it has none. `build.rs` names the generated tests the same way.
+fn default_name(idx: usize, count: usize) -> String {
+    if count == 1 {
+        "example".into()
+    } else {
+        format!("example_{}", idx + 1)
+    }
+}
+
+/// Parses the cases of an example file.
+pub fn parse_example_cases(src: &str, day: Day) -> Result<Vec<ExampleCase>, String> {
+    let (defaults, cases) = parse_tables(src)?;
+    let count = cases.len();
+    Ok(cases
+        .into_iter()
+        .enumerate()
+        .map(|(idx, params)| ExampleCase::new(day, idx, count, params, &defaults))
+        .collect())
+}
+
+/// Reads the cases declared in `data/examples/NN.toml`, or none if the file does not exist.
+#[must_use]
+pub fn read_example_cases(day: Day) -> Vec<ExampleCase> {
+    let filepath = data_path("examples", &format!("{day}.toml"));
+    let Ok(src) = fs::read_to_string(&filepath) else {
+        return vec![];
+    };
+    parse_example_cases(&src, day)
+        .unwrap_or_else(|err| panic!("could not parse {}: {err}", filepath.display()))
+}
+
+/// Runs the example case at `idx` and panics unless every part with an expected answer
+/// produces it. A case without expected answers is not run, so a freshly scaffolded day with an
+/// empty example passes. This is the body of the tests generated by [`solution!`](crate::solution).
+pub fn check_example_case<S: Solution>(mut solution: S, day: Day, idx: usize) {
+    let case = read_example_cases(day)
+        .into_iter()
+        .nth(idx)
+        .unwrap_or_else(|| {
+            let filepath = data_path("examples", &format!("{day}.toml"));
+            panic!("{} has no case {}", filepath.display(), idx + 1)
+        });
+    if case.part_one.is_none() && case.part_two.is_none() {
+        return;
+    }
+    solution.configure(&case.params);
+    let filepath = data_path("examples", &case.input);
+    let input = fs::read_to_string(&filepath)
+        .map(|input| normalize_input(&input))
+        .unwrap_or_else(|err| panic!("could not open {}: {err}", filepath.display()));
+    if let Err(err) = solution.validate(&input) {
+        panic!("{}: invalid input: {err}", case.name);
+    }
+    let parsed = solution.parse(&input);
+    if let Some(expected) = &case.part_one {
+        check_answer(solution.part_one(&parsed), expected, &case.name, 1);
+    }
+    if let Some(expected) = &case.part_two {
+        check_answer(solution.part_two(&parsed), expected, &case.name, 2);
+    }
+}
+
+/// Runs the solution on an input from the day's [`generator`](crate::generators::generator), as
+/// large as the real input, and panics if `validate` rejects it, or if parsing or a part panics
+/// or fails on it. Does nothing for days without a generator. This is the body of a test
+/// generated by [`solution!`](crate::solution).
+pub fn check_generated_inputs<S: Solution>(solution: S, day: Day) {
+    const SEED: u64 = 1;
+    let Some(generator) = generator(day) else {
+        return;
+    };
+    let size = generator.default_size;
+    let input = normalize_input(&(generator.generate)(&mut Rng::new(SEED), size));
+    let case = format!(
+        "generated input of {size} {} (`cargo generate {day} --seed {SEED}`)",
+        generator.unit
+    );
+    if let Err(err) = solution.validate(&input) {
+        panic!("{case}: invalid input: {err}");
+    }
+    let parsed = catch_panic(|| solution.parse(&input))
+        .unwrap_or_else(|message| panic!("{case}: parse failed: {message}"));
+    let part_one =
+        catch_panic(|| solution.part_one(&parsed).into_outcome()).unwrap_or_else(Outcome::Failed);
+    if let Outcome::Failed(message) = part_one {
+        panic!("{case}: part 1 failed: {message}");
+    }
+    let part_two =
+        catch_panic(|| solution.part_two(&parsed).into_outcome()).unwrap_or_else(Outcome::Failed);
+    if let Outcome::Failed(message) = part_two {
+        panic!("{case}: part 2 failed: {message}");
+    }
+}
+
+fn check_answer(answer: impl Answer, expected: &str, case: &str, part: u8) {
+    match answer.into_outcome() {
+        Outcome::Solved(answer) => assert_eq!(
+            answer.to_string(),
+            expected,
+            "{case}: wrong answer for part {part}"
+        ),
+        Outcome::Unsolved => panic!("{case}: part {part} has no answer, expected {expected}"),
+        Outcome::Failed(message) => panic!("{case}: part {part} failed: {message}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_example_cases;
+    use crate::day;
+    use crate::template::Params;
+
+    #[test]
+    fn parses_cases_with_defaults() {
+        let src = "to_connect = 10\n\n[[case]]\npart_one = 40\npart_two = 25272\n\n[[case]]\nname = \"single_pair\"\ninput = \"08-2.txt\"\nto_connect = 1\npart_one = 2\n";
+        let cases = parse_example_cases(src, day!(8)).unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].name, "example_1");
+        assert_eq!(cases[0].input, "08.txt");
+        assert_eq!(cases[0].part_one.as_deref(), Some("40"));
+        assert_eq!(cases[0].part_two.as_deref(), Some("25272"));
+        assert_eq!(cases[0].params, Params::default().with("to_connect", 10));
+        assert_eq!(cases[1].name, "single_pair");
+        assert_eq!(cases[1].input, "08-2.txt");
+        assert_eq!(cases[1].part_two, None);
+        assert_eq!(cases[1].params, Params::default().with("to_connect", 1));
+    }
+
+    #[test]
+    fn names_a_single_case_example() {
+        let cases = parse_example_cases("[[case]]\npart_one = 3\n", day!(1)).unwrap();
+        assert_eq!(cases[0].name, "example");
+        assert!(
+            parse_example_cases("to_connect = 10\n", day!(8))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,511 @@
+//! Exports of the stored benchmarks, for sharing them outside of the repository.
+//!
+//! CSV and JSON Lines have one row per day. The HTML report is a single file without external
+//! resources: it charts the latest timings of every day and how they developed over the runs
+//! stored with `cargo time --store`, next to the machine the report was made on.
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::environment::Environment;
+use crate::template::timings::{Recorded, Stats, Timing, Timings, format_nanos};
+
+/// Format to export benchmarks to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "html" => Ok(ExportFormat::Html),
+            _ => Err(format!(
+                "unknown export format `{s}`, expected `csv`, `jsonl` or `html`."
+            )),
+        }
+    }
+}
+
+/// Renders `timings` in `format`. Only the HTML report shows the `history`.
+pub(crate) fn render(format: ExportFormat, timings: &Timings, history: &[Recorded]) -> String {
+    match format {
+        ExportFormat::Csv => to_csv(timings),
+        ExportFormat::Jsonl => to_jsonl(timings),
+        ExportFormat::Html => to_html(timings, history, now()),
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn average(stats: Option<&Stats>) -> Option<u64> {
+    stats.map(|stats| stats.average)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn as_f64(nanos: u64) -> f64 {
+    nanos as f64
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn to_csv(timings: &Timings) -> String {
+    let mut csv = String::from(
+        "day,parse_ns,part_1_ns,part_2_ns,total_ns,\
+        part_1_min_ns,part_1_median_ns,part_1_samples,part_1_allocations,part_1_peak_bytes,part_1_status,\
+        part_2_min_ns,part_2_median_ns,part_2_samples,part_2_allocations,part_2_peak_bytes,part_2_status,\
+        commit,dirty\n",
+    );
+    let ns = |nanos: Option<u64>| nanos.map_or(String::new(), |n| n.to_string());
+
+    for timing in &timings.data {
+        let mut fields = vec![
+            timing.day.to_string(),
+            ns(average(timing.parse.as_ref())),
+            ns(average(timing.part_1.as_ref())),
+            ns(average(timing.part_2.as_ref())),
+            timing.total_nanos.to_string(),
+        ];
+        for (stats, status) in [timing.part_1, timing.part_2].iter().zip(timing.status) {
+            fields.extend([
+                ns(stats.and_then(|s| s.min)),
+                ns(stats.and_then(|s| s.median)),
+                ns(stats.and_then(|s| s.samples)),
+                ns(stats.and_then(|s| s.allocations).map(|a| a.count)),
+                ns(stats.and_then(|s| s.allocations).map(|a| a.peak_bytes)),
+                status.map_or(String::new(), |s| s.as_str().to_string()),
+            ]);
+        }
+        let environment = timing.environment.as_ref();
+        fields.extend([
+            environment
+                .and_then(|e| e.commit.clone())
+                .unwrap_or_default(),
+            environment.map_or(String::new(), |e| e.dirty.to_string()),
+        ]);
+        writeln!(csv, "{}", fields.join(",")).unwrap();
+    }
+    csv
+}
+
+fn to_jsonl(timings: &Timings) -> String {
+    timings
+        .data
+        .iter()
+        .map(|timing| JsonValue::from(timing).stringify().unwrap() + "\n")
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+const STAGES: [(&str, &str); 3] = [
+    ("Parse", "#9aa5b1"),
+    ("Part 1", "#2f80ed"),
+    ("Part 2", "#f2994a"),
+];
+
+fn to_html(timings: &Timings, history: &[Recorded], at: u64) -> String {
+    let total: u64 = timings.data.iter().map(|t| t.total_nanos).sum();
+
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>Advent of Code benchmarks</title>\n<style>\n\
+        body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 60rem; color: #1f2933; }\n\
+        table { border-collapse: collapse; margin: 1rem 0; }\n\
+        th, td { padding: 0.25rem 0.75rem; border-bottom: 1px solid #e4e7eb; text-align: right; }\n\
+        th:first-child, td:first-child { text-align: left; }\n\
+        svg text { font-size: 12px; fill: #52606d; }\n\
+        .legend span { display: inline-block; width: 0.8rem; height: 0.8rem; margin: 0 0.3rem 0 1rem; }\n\
+        </style>\n</head>\n<body>\n<h1>🎄 Advent of Code benchmarks</h1>\n",
+    );
+    writeln!(
+        html,
+        "<p>{} days, {} in total. Exported on {}.</p>",
+        timings.data.len(),
+        format_nanos(total),
+        format_date(at)
+    )
+    .unwrap();
+
+    // timings are exported for one environment, only the commit differs between days.
+    html.push_str("<h2>Machine</h2>\n");
+    match timings.data.iter().find_map(|t| t.environment.as_ref()) {
+        Some(environment) => {
+            html.push_str("<table>\n");
+            for (key, value) in environment.fields() {
+                writeln!(html, "<tr><th>{key}</th><td>{}</td></tr>", escape(&value)).unwrap();
+            }
+            html.push_str("</table>\n");
+        }
+        None => html.push_str("<p>Unknown, these timings were stored without it.</p>\n"),
+    }
+
+    html.push_str("<h2>Timings per day</h2>\n<p class=\"legend\">");
+    for (name, color) in STAGES {
+        write!(html, "<span style=\"background:{color}\"></span>{name}").unwrap();
+    }
+    html.push_str("</p>\n");
+    html.push_str(&day_chart(timings));
+    html.push_str(&details_table(timings, history));
+
+    if !history.is_empty() {
+        html.push_str("<h2>History</h2>\n<p>Total of the latest timing of every day after each stored run.</p>\n");
+        html.push_str(&history_chart(history));
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Horizontal bars of the parse stage and both parts of each day, scaled to the slowest day.
+fn day_chart(timings: &Timings) -> String {
+    const LABEL: f64 = 60.0;
+    const WIDTH: f64 = 640.0;
+    const ROW: f64 = 22.0;
+
+    let stages =
+        |timing: &Timing| [timing.parse, timing.part_1, timing.part_2].map(|s| average(s.as_ref()));
+    let max = timings
+        .data
+        .iter()
+        .map(|t| stages(t).iter().flatten().sum::<u64>().max(t.total_nanos))
+        .max()
+        .map_or(0.0, as_f64);
+    #[allow(clippy::cast_precision_loss)]
+    let height = ROW * timings.data.len() as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{height}\" role=\"img\">\n",
+        LABEL + WIDTH + 80.0
+    );
+    for (row, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = ROW * row as f64;
+        writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\">Day {}</text>",
+            y + 15.0,
+            timing.day.into_inner()
+        )
+        .unwrap();
+
+        let mut x = LABEL;
+        for ((name, color), stage) in STAGES.iter().zip(stages(timing)) {
+            let Some(stage) = stage else { continue };
+            let width = if max > 0.0 {
+                as_f64(stage) / max * WIDTH
+            } else {
+                0.0
+            };
+            writeln!(
+                svg,
+                "<rect x=\"{x:.1}\" y=\"{}\" width=\"{width:.1}\" height=\"{}\" fill=\"{color}\"><title>Day {} {name}: {}</title></rect>",
+                y + 3.0,
+                ROW - 6.0,
+                timing.day.into_inner(),
+                format_nanos(stage)
+            )
+            .unwrap();
+            x += width;
+        }
+        writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{}\">{}</text>",
+            x + 6.0,
+            y + 15.0,
+            format_nanos(timing.total_nanos)
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn details_table(timings: &Timings, history: &[Recorded]) -> String {
+    let mut table = String::from(
+        "<table>\n<tr><th>Day</th><th>Parse</th><th>Part 1</th><th>Part 1 min / median</th>\
+        <th>Part 2</th><th>Part 2 min / median</th><th>Total</th><th>Trend</th><th>Commit</th></tr>\n",
+    );
+    let cell = |nanos: Option<u64>| nanos.map_or_else(|| "-".into(), format_nanos);
+    let spread = |stats: Option<&Stats>| match stats.map(|s| (s.min, s.median)) {
+        None | Some((None, None)) => "-".into(),
+        Some((min, median)) => format!("{} / {}", cell(min), cell(median)),
+    };
+
+    for timing in &timings.data {
+        let trend: Vec<u64> = history
+            .iter()
+            .filter(|r| r.timing.day == timing.day)
+            .map(|r| r.timing.total_nanos)
+            .collect();
+        let commit = timing
+            .environment
+            .as_ref()
+            .map_or("-".into(), Environment::revision);
+        writeln!(
+            table,
+            "<tr><td>Day {}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            timing.day.into_inner(),
+            cell(average(timing.parse.as_ref())),
+            cell(average(timing.part_1.as_ref())),
+            spread(timing.part_1.as_ref()),
+            cell(average(timing.part_2.as_ref())),
+            spread(timing.part_2.as_ref()),
+            format_nanos(timing.total_nanos),
+            sparkline(&trend),
+            escape(&commit)
+        )
+        .unwrap();
+    }
+    table.push_str("</table>\n");
+    table
+}
+
+/// A small line of how the total time of a day changed over its stored runs.
+fn sparkline(values: &[u64]) -> String {
+    const WIDTH: f64 = 80.0;
+    const HEIGHT: f64 = 16.0;
+
+    if values.len() < 2 {
+        return String::new();
+    }
+    let max = values.iter().copied().max().unwrap_or_default();
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\"><polyline fill=\"none\" stroke=\"#2f80ed\" points=\"{}\"/></svg>",
+        points(values, max, WIDTH, HEIGHT)
+    )
+}
+
+/// The total of the latest timing of every day after each stored run, plotted against the runs.
+fn history_chart(history: &[Recorded]) -> String {
+    const LEFT: f64 = 80.0;
+    const WIDTH: f64 = 640.0;
+    const HEIGHT: f64 = 200.0;
+
+    let mut latest: BTreeMap<_, u64> = BTreeMap::new();
+    let mut runs: Vec<(u64, u64)> = vec![];
+    for (idx, recorded) in history.iter().enumerate() {
+        latest.insert(recorded.timing.day, recorded.timing.total_nanos);
+        // a run stores all of its days at once.
+        let run_ends = history
+            .get(idx + 1)
+            .is_none_or(|next| next.recorded_at != recorded.recorded_at);
+        if run_ends {
+            runs.push((recorded.recorded_at, latest.values().sum()));
+        }
+    }
+
+    let totals: Vec<u64> = runs.iter().map(|(_, total)| *total).collect();
+    let max = totals.iter().copied().max().unwrap_or_default();
+    let (first, last) = (runs[0].0, runs[runs.len() - 1].0);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" role=\"img\">\n",
+        LEFT + WIDTH,
+        HEIGHT + 30.0
+    );
+    writeln!(
+        svg,
+        "<text x=\"0\" y=\"12\">{}</text>\n<text x=\"0\" y=\"{HEIGHT}\">0</text>",
+        format_nanos(max)
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<line x1=\"{LEFT}\" y1=\"{HEIGHT}\" x2=\"{}\" y2=\"{HEIGHT}\" stroke=\"#cbd2d9\"/>",
+        LEFT + WIDTH
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<g transform=\"translate({LEFT} 0)\"><polyline fill=\"none\" stroke=\"#2f80ed\" stroke-width=\"2\" points=\"{}\"/></g>",
+        points(&totals, max, WIDTH, HEIGHT)
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<text x=\"{LEFT}\" y=\"{}\">{}</text>\n<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+        HEIGHT + 20.0,
+        format_date(first),
+        LEFT + WIDTH,
+        HEIGHT + 20.0,
+        format_date(last)
+    )
+    .unwrap();
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// SVG points spreading `values` evenly over `width`, with `max` at the top of `height`.
+fn points(values: &[u64], max: u64, width: f64, height: f64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let step = width / (values.len().max(2) - 1) as f64;
+    values
+        .iter()
+        .enumerate()
+        .map(|(idx, value)| {
+            let y = if max > 0 {
+                as_f64(*value) / as_f64(max)
+            } else {
+                0.0
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let x = step * idx as f64;
+            format!("{x:.1},{:.1}", height - y * (height - 2.0))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `secs` since the unix epoch as a `YYYY-MM-DD` date in UTC.
+fn format_date(secs: u64) -> String {
+    // see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    #[allow(clippy::cast_possible_wrap)]
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_date, to_csv, to_html, to_jsonl};
+    use crate::day;
+    use crate::template::allocations::Allocations;
+    use crate::template::environment::Environment;
+    use crate::template::timings::{PartStatus, Recorded, Stats, Timing, Timings};
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some(Stats {
+                        average: 10_000_000,
+                        min: Some(9_500_000),
+                        median: Some(9_900_000),
+                        samples: Some(10),
+                        allocations: Some(Allocations {
+                            count: 12,
+                            peak_bytes: 4096,
+                        }),
+                        counters: None,
+                    }),
+                    part_2: Some(Stats::from_average(20_000)),
+                    total_nanos: 10_020_000,
+                    parse: None,
+                    status: [Some(PartStatus::Solved), None],
+                    environment: Some(Environment {
+                        cpu: "<fast>".into(),
+                        cores: 8,
+                        rustc: "rustc 1.91.0".into(),
+                        profile: "release".into(),
+                        target_features: "neon".into(),
+                        allocator: "counting".into(),
+                        commit: Some("abc1234".into()),
+                        dirty: true,
+                    }),
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some(Stats::from_average(1_000_000_000)),
+                    part_2: None,
+                    total_nanos: 1_000_000_000,
+                    parse: Some(Stats::from_average(150)),
+                    status: Default::default(),
+                    environment: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = to_csv(&get_mock_timings());
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("day,parse_ns,part_1_ns,part_2_ns,total_ns,"));
+        assert_eq!(
+            lines[1],
+            "01,,10000000,20000,10020000,9500000,9900000,10,12,4096,solved,,,,,,,abc1234,true"
+        );
+        assert_eq!(lines[2], "02,150,1000000000,,1000000000,,,,,,,,,,,,,,");
+    }
+
+    #[test]
+    fn exports_json_lines() {
+        let jsonl = to_jsonl(&get_mock_timings());
+        let timings: Vec<Timing> = jsonl
+            .lines()
+            .map(|line| Timing::try_from(&line.parse::<tinyjson::JsonValue>().unwrap()).unwrap())
+            .collect();
+        assert_eq!(timings.len(), 2);
+        assert_eq!(timings[1].parse, Some(Stats::from_average(150)));
+    }
+
+    #[test]
+    fn exports_standalone_html() {
+        let timings = get_mock_timings();
+        let history: Vec<Recorded> = [(100, 0), (200, 0), (200, 1)]
+            .into_iter()
+            .map(|(recorded_at, idx)| Recorded {
+                recorded_at,
+                timing: timings.data[idx].clone(),
+            })
+            .collect();
+        let html = to_html(&timings, &history, 0);
+        assert!(html.contains("Exported on 1970-01-01."));
+        assert!(html.contains("<td>&lt;fast&gt;</td>"));
+        assert!(html.contains("<td>abc1234 (dirty)</td>"));
+        assert!(html.contains("<title>Day 2 Part 1: 1.0s</title>"));
+        assert!(html.contains("<h2>History</h2>"));
+        assert!(!html.contains("src=") && !html.contains("href="));
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1_733_011_200), "2024-12-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+    }
+}
@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Normalizes line endings to `\n` and the end of the input to exactly one newline, so solutions
+/// can rely on every line, including the last, ending in `\n`. Trailing spaces within lines are
+/// kept, as some inputs are laid out in columns.
+#[must_use]
+pub fn normalize_input(input: &str) -> String {
+    let mut normalized = input.replace("\r\n", "\n");
+    let end = normalized.trim_end_matches('\n').len();
+    normalized.truncate(end);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// An input that does not match what a day expects, returned by [`Solution::validate`].
+///
+/// [`Solution::validate`]: crate::template::Solution::validate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    /// 1-based line number, or [`None`] if the problem is not with a single line.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl InputError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Checks every line of `input` with `check`, reporting the first failure with its line number.
+/// `first_line` is the line number of the first line of `input` in the whole input.
+pub fn check_lines(
+    input: &str,
+    first_line: usize,
+    mut check: impl FnMut(&str) -> Result<(), String>,
+) -> Result<(), InputError> {
+    if input.is_empty() {
+        return Err(InputError::new("input is empty"));
+    }
+    for (idx, line) in input.lines().enumerate() {
+        check(line).map_err(|message| InputError::at_line(first_line + idx, message))?;
+    }
+    Ok(())
+}
+
+/// Checks that `input` is a rectangular grid made of the bytes in `cells`.
+pub fn check_grid(input: &str, first_line: usize, cells: &[u8]) -> Result<(), InputError> {
+    let width = input.lines().next().map_or(0, str::len);
+    check_lines(input, first_line, |line| {
+        if line.len() != width {
+            return Err(format!("expected {width} cells, found {}", line.len()));
+        }
+        match line.bytes().find(|cell| !cells.contains(cell)) {
+            Some(cell) => Err(format!("unexpected cell `{}`", char::from(cell))),
+            None => Ok(()),
+        }
+    })
+}
+
+/// Matches `line` against `pattern`, where `{}` stands for an unsigned integer and every other
+/// character for itself, and returns the integers.
+pub fn match_pattern(line: &str, pattern: &str) -> Result<Vec<u64>, String> {
+    let mismatch = || format!("expected `{pattern}`, found `{line}`");
+    let mut numbers = vec![];
+    let mut rest = line;
+    let mut literals = pattern.split("{}").peekable();
+    while let Some(literal) = literals.next() {
+        rest = rest.strip_prefix(literal).ok_or_else(mismatch)?;
+        if literals.peek().is_none() {
+            break;
+        }
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let number = rest[..digits].parse().map_err(|_| mismatch())?;
+        numbers.push(number);
+        rest = &rest[digits..];
+    }
+    if rest.is_empty() {
+        Ok(numbers)
+    } else {
+        Err(mismatch())
+    }
+}
+
+/// Splits `input` into the sections separated by blank lines, with the line number each section
+/// starts at.
+pub fn sections(input: &str) -> Vec<(usize, &str)> {
+    let mut line = 1;
+    input
+        .split("\n\n")
+        .map(|section| {
+            let start = line;
+            line += section.lines().count() + 1;
+            (start, section)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputError, check_grid, check_lines, match_pattern, normalize_input, sections};
+
+    #[test]
+    fn normalizes_line_endings_and_trailing_newlines() {
+        assert_eq!(normalize_input("L68\r\nR48"), "L68\nR48\n");
+        assert_eq!(normalize_input("L68\nR48\n\n\n"), "L68\nR48\n");
+        assert_eq!(normalize_input("12 3 \n* + \n\n"), "12 3 \n* + \n");
+        assert_eq!(normalize_input(""), "");
+    }
+
+    #[test]
+    fn matches_patterns() {
+        assert_eq!(match_pattern("L68", "L{}"), Ok(vec![68]));
+        assert_eq!(
+            match_pattern("4x4: 0 2", "{}x{}: {} {}"),
+            Ok(vec![4, 4, 0, 2])
+        );
+        assert_eq!(
+            match_pattern("4x4: 0 2 1", "{}x{}: {} {}"),
+            Err("expected `{}x{}: {} {}`, found `4x4: 0 2 1`".into())
+        );
+        assert!(match_pattern("Lx", "L{}").is_err());
+        assert!(match_pattern("12x5:", "{}x{}: {}").is_err());
+        assert!(match_pattern("", "{}").is_err());
+    }
+
+    #[test]
+    fn reports_line_numbers() {
+        let input = "..@\n.@.\n@@\n";
+        assert_eq!(
+            check_grid(input, 1, b".@"),
+            Err(InputError::at_line(3, "expected 3 cells, found 2"))
+        );
+        assert_eq!(
+            check_lines("", 1, |_| Ok(())),
+            Err(InputError::new("input is empty"))
+        );
+        let parts = sections("0:\n###\n\n1:\n#..\n\n4x4: 0 2\n");
+        assert_eq!(
+            parts.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+            [1, 4, 7]
+        );
+        assert_eq!(
+            InputError::at_line(7, "expected `{}x{}`").to_string(),
+            "line 7: expected `{}x{}`"
+        );
+    }
+}
@@ -1,44 +1,94 @@
-use std::{env, fs};
+use std::io::{self, Read};
+use std::path::Path;
 
+use params::read_params_file;
+
+pub mod allocations;
 pub mod aoc_cli;
 pub mod commands;
+pub mod differential;
+pub mod encryption;
+pub mod export;
 pub mod runner;
 
 pub use day::*;
+pub use examples::*;
+pub use input::*;
+pub use params::*;
+pub use paths::*;
+pub use run_multi::RunOptions;
+pub use selection::DaySelection;
+pub use solution::*;
 
+mod answers;
+mod counters;
+mod cpu_profile;
 mod day;
+mod environment;
+mod examples;
+mod input;
+mod params;
+mod paths;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod selection;
+mod solution;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file to a string, see [`normalize_input`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_path(&data_path(folder, &format!("{day}.txt")))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_path(&data_path(folder, &format!("{day}-{part}.txt")))
+}
+
+/// Reads the input to solve `day` with and its parameters: the file at `path` if one is given
+/// (`-` for stdin), or the downloaded input otherwise. A file given by path takes its parameters
+/// from a sidecar file with the same name and a `.toml` extension, if there is one.
+#[must_use]
+pub fn read_input(day: Day, path: Option<&str>) -> (String, Params) {
+    match path {
+        None => (read_file("inputs", day), read_params("inputs", day)),
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .expect("could not read input from stdin");
+            (normalize_input(&input), Params::default())
+        }
+        Some(path) => {
+            let path = Path::new(path);
+            (
+                read_path(path),
+                read_params_file(&path.with_extension("toml")),
+            )
+        }
+    }
+}
+
+fn read_path(path: &Path) -> String {
+    let input = encryption::read_input_file(path)
+        .unwrap_or_else(|err| panic!("could not open input file {}: {err}", path.display()));
+    normalize_input(&input)
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// With only a day, the solution is made of the `part_one`, `part_one_tui`, `part_two` and
+/// `part_two_tui` functions in scope. A second parameter passes any [`Solution`] instead.
+///
+/// In tests, it also generates one test per case of the day's example file, see [`ExampleCase`],
+/// and one that runs the solution on generated inputs, see [`check_generated_inputs`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -46,18 +96,64 @@ macro_rules! solution {
     };
 
     (@impl $day:expr, [$func1:expr,$func1_tui:expr,$func2:expr,$func2_tui:expr]) => {
+        $crate::solution!(
+            $day,
+            $crate::template::FreeFunctions::new($func1, $func1_tui, $func2, $func2_tui)
+        );
+    };
+
+    ($day:expr, $solution:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// One test per case in `data/examples/NN.toml`, generated by `build.rs`.
+        #[cfg(test)]
+        mod example_cases {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[allow(dead_code)]
+            fn solution() -> impl $crate::template::Solution {
+                $solution
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/example_cases/", $day, ".rs"));
+        }
+
+        /// Checks the solution against inputs from the generator of the day.
+        #[cfg(test)]
+        mod generated_inputs {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn solves_generated_inputs() {
+                $crate::template::check_generated_inputs($solution, DAY);
+            }
+        }
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "allocations", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::allocations::CountingAlloc =
+            $crate::template::allocations::CountingAlloc;
+
         fn main() {
             use std::{env,process};
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+            use $crate::template::{Solution, runner::*};
             let args: Vec<String> = env::args().collect();
+            let input_path = args.iter().position(|x| x == "--input").map(|idx| {
+                args.get(idx + 1).map(String::as_str).unwrap_or_else(|| {
+                    eprintln!("Expected a path after --input, or `-` for stdin.");
+                    process::exit(1);
+                })
+            });
+            let (input, params) = $crate::template::read_input(DAY, input_path);
+            let mut solution = $solution;
+            Solution::configure(&mut solution, &params);
             if args.contains(&"--tui".into()) {
                 let part_index = args.iter().position(|x| x == "--part").expect("part number expected") + 1;
                 match args[part_index].parse::<u8>(){
@@ -65,18 +161,17 @@ macro_rules! solution {
                         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
                         process::exit(1);
                     },
-                    Ok(1)=> $func1_tui(&input).unwrap(),
-                    Ok(2)=> $func2_tui(&input).unwrap(),
+                    Ok(1)=> Solution::part_one_tui(&solution, &input).unwrap(),
+                    Ok(2)=> Solution::part_two_tui(&solution, &input).unwrap(),
                     _=>{
                         eprintln!("Part must be 1 or 2");
                         process::exit(1);
                     }
                 };
 
-            }else{
-                run_part($func1, &input, DAY,1);
-                run_part($func2, &input, DAY,2);
-        }
+            }else if !run_solution(&solution, &input, DAY) {
+                process::exit(1);
+            }
         }
     };
 }
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+use std::{fs, io};
+
+use crate::template::{Day, data_path};
+
+/// Per-input parameters of a day, e.g. the number of steps to simulate, which often differs
+/// between the example and the real input.
+///
+/// They are read from an optional sidecar file next to the input, `data/examples/08.toml` for
+/// the example of day 8, holding one `key = value` pair per line:
+///
+/// ```toml
+/// # the example connects fewer boxes than the real input
+/// to_connect = 10
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: HashMap<String, String>,
+}
+
+impl Params {
+    /// Parses `key = value` lines. Blank lines and `#` comments are skipped, and string values
+    /// may be quoted. The `[[case]]` tables of example files are skipped as well, see
+    /// [`read_example_cases`](crate::template::read_example_cases).
+    pub fn parse(src: &str) -> Result<Self, String> {
+        parse_tables(src).map(|(params, _)| params)
+    }
+
+    /// Sets `key`, replacing any previous value.
+    #[must_use]
+    pub fn with(mut self, key: &str, value: impl Display) -> Self {
+        self.values.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// The value of `key`, or [`None`] if it is not set.
+    ///
+    /// # Panics
+    /// Panics if the value does not parse as `T`.
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.values.get(key).map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("parameter `{key}` has an invalid value `{value}`"))
+        })
+    }
+
+    /// The value of `key`, or `default` if it is not set.
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> T {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Removes `key`, returning its raw value.
+    pub(crate) fn take(&mut self, key: &str) -> Option<String> {
+        self.values.remove(key)
+    }
+
+    /// Sets every value of `other`, replacing values that are set in both.
+    pub(crate) fn extend(&mut self, other: Params) {
+        self.values.extend(other.values);
+    }
+}
+
+/// Parses the top-level `key = value` pairs and the pairs of each `[[case]]` table.
+pub(crate) fn parse_tables(src: &str) -> Result<(Params, Vec<Params>), String> {
+    let mut top = Params::default();
+    let mut cases: Vec<Params> = vec![];
+    for (idx, line) in src.lines().enumerate() {
+        let line = line.split_once(" #").map_or(line, |(line, _)| line).trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            if line != "[[case]]" {
+                return Err(format!("line {}: unknown table `{line}`", idx + 1));
+            }
+            cases.push(Params::default());
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected `key = value`", idx + 1));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("line {}: missing key", idx + 1));
+        }
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+        cases
+            .last_mut()
+            .unwrap_or(&mut top)
+            .values
+            .insert(key.to_string(), value.to_string());
+    }
+    Ok((top, cases))
+}
+
+/// Reads the parameters for the input of `day` in `folder`. Inputs without a sidecar file have
+/// no parameters.
+#[must_use]
+pub fn read_params(folder: &str, day: Day) -> Params {
+    read_params_file(&data_path(folder, &format!("{day}.toml")))
+}
+
+/// Reads the parameters in the sidecar file at `filepath`, or none if it does not exist.
+pub(crate) fn read_params_file(filepath: &Path) -> Params {
+    match fs::read_to_string(filepath) {
+        Ok(src) => Params::parse(&src)
+            .unwrap_or_else(|err| panic!("could not parse {}: {err}", filepath.display())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Params::default(),
+        Err(err) => panic!("could not open {}: {err}", filepath.display()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Params;
+
+    #[test]
+    fn parses_key_value_lines() {
+        let params = Params::parse(
+            "# example parameters\n\nto_connect = 10 # boxes\nname = \"example\"\nsteps=6\n",
+        )
+        .unwrap();
+        assert_eq!(params.get::<usize>("to_connect"), Some(10));
+        assert_eq!(params.get::<String>("name").as_deref(), Some("example"));
+        assert_eq!(params.get_or("steps", 64), 6);
+        assert_eq!(params.get_or("missing", 64), 64);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert_eq!(
+            Params::parse("to_connect 10"),
+            Err("line 1: expected `key = value`".into())
+        );
+        assert_eq!(
+            Params::parse("a = 1\n= 2"),
+            Err("line 2: missing key".into())
+        );
+        assert_eq!(
+            Params::parse("[params]\na = 1"),
+            Err("line 1: unknown table `[params]`".into())
+        );
+    }
+
+    #[test]
+    fn skips_case_tables() {
+        let params = Params::parse("steps = 6\n\n[[case]]\nsteps = 10\npart_one = 16\n").unwrap();
+        assert_eq!(params, Params::default().with("steps", 6));
+    }
+
+    #[test]
+    fn overrides_values() {
+        let params = Params::default().with("to_connect", 10);
+        assert_eq!(params.get::<u32>("to_connect"), Some(10));
+    }
+}
@@ -0,0 +1,26 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Root of the project, the directory of `Cargo.toml`. Paths are resolved from here rather than
+/// from the working directory, so solutions and tests find their data wherever they are run from.
+#[must_use]
+pub fn project_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Directory holding the inputs, examples and puzzles. Defaults to `data` and can be moved with
+/// the `AOC_DATA_DIR` environment variable, e.g. in the `[env]` section of `.cargo/config.toml`.
+/// Relative paths are relative to [`project_dir`].
+#[must_use]
+pub fn data_dir() -> PathBuf {
+    match env::var_os("AOC_DATA_DIR") {
+        Some(dir) if !dir.is_empty() => project_dir().join(dir),
+        _ => project_dir().join("data"),
+    }
+}
+
+/// Path of `file` in the `folder` of the [`data_dir`], e.g. `data_path("inputs", "01.txt")`.
+#[must_use]
+pub fn data_path(folder: &str, file: &str) -> PathBuf {
+    data_dir().join(folder).join(file)
+}
@@ -1,9 +1,11 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, str::FromStr};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::allocations::format_bytes;
+use crate::template::environment::Environment;
+use crate::template::timings::{PartStatus, Timing, Timings, format_nanos};
+use crate::template::{Day, Params, project_dir};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -30,8 +32,10 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the table between a pair of `marker` comments, or the single `marker` a table has not
+/// been written to yet.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,76 +56,420 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// A column of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column {
+    Day,
+    Parse,
+    /// Average time of a part, 1 or 2.
+    Part(u8),
+    Min(u8),
+    Median(u8),
+    Samples(u8),
+    /// Number of allocations of a part.
+    Allocations(u8),
+    /// Most bytes a part had allocated at once.
+    Peak(u8),
+    Total,
+    /// ✔ for each part that produced an answer, ✖ for those that did not.
+    Status,
+    /// ⭐ for each part that produced an answer.
+    Stars,
+    /// A bar of the total time, relative to the slowest day.
+    Cost,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let column = match s {
+            "day" => Column::Day,
+            "parse" => Column::Parse,
+            "part_1" => Column::Part(1),
+            "part_2" => Column::Part(2),
+            "min_1" => Column::Min(1),
+            "min_2" => Column::Min(2),
+            "median_1" => Column::Median(1),
+            "median_2" => Column::Median(2),
+            "samples_1" => Column::Samples(1),
+            "samples_2" => Column::Samples(2),
+            "allocs_1" => Column::Allocations(1),
+            "allocs_2" => Column::Allocations(2),
+            "peak_1" => Column::Peak(1),
+            "peak_2" => Column::Peak(2),
+            "total" => Column::Total,
+            "status" => Column::Status,
+            "stars" => Column::Stars,
+            "cost" => Column::Cost,
+            _ => return Err(format!("unknown column `{s}`")),
+        };
+        Ok(column)
+    }
+}
+
+impl Column {
+    fn header(self) -> String {
+        match self {
+            Column::Day => "Day".into(),
+            Column::Parse => "Parse".into(),
+            Column::Part(part) => format!("Part {part}"),
+            Column::Min(part) => format!("Min {part}"),
+            Column::Median(part) => format!("Median {part}"),
+            Column::Samples(part) => format!("Samples {part}"),
+            Column::Allocations(part) => format!("Allocs {part}"),
+            Column::Peak(part) => format!("Peak {part}"),
+            Column::Total => "Total".into(),
+            Column::Status => "Status".into(),
+            Column::Stars => "Stars".into(),
+            Column::Cost => "Cost".into(),
+        }
+    }
+
+    fn alignment(self) -> &'static str {
+        match self {
+            Column::Cost => ":---",
+            _ => ":---:",
+        }
+    }
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    /// Nanoseconds a day spends on what the column shows, to sort and total by.
+    fn nanos(self, timing: &Timing) -> Option<u64> {
+        match self {
+            Column::Day => Some(u64::from(timing.day.into_inner())),
+            Column::Parse => timing.parse.map(|stats| stats.average),
+            Column::Part(part) => timing.part(part).map(|stats| stats.average),
+            Column::Total | Column::Cost => Some(timing.total_nanos),
+            _ => None,
+        }
+    }
+}
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+/// Whether a part produced an answer. Timings stored before statuses were recorded only know
+/// about parts that have a time.
+fn is_solved(timing: &Timing, part: u8) -> bool {
+    match timing.status[usize::from(part - 1)] {
+        Some(status) => status == PartStatus::Solved,
+        None => timing.part(part).is_some(),
+    }
+}
+
+/// How the benchmark table in the README is laid out, read from `templates/benchmarks.md`.
+///
+/// The file starts with `key = value` settings between `---` lines, followed by the Markdown
+/// that replaces the table markers:
+///
+/// ```md
+/// ---
+/// columns = day, parse, part_1, part_2, total, cost
+/// sort = total
+/// order = desc
+/// totals_row = true
+/// ---
+/// ## Benchmarks
+///
+/// {table}
+///
+/// **Total: {total}**
+/// ```
+///
+/// `columns` are any of `day`, `parse`, `part_1`, `part_2`, `min_N`, `median_N`, `samples_N`,
+/// `allocs_N`, `peak_N`, `total`, `status`, `stars` and `cost`. Tables are sorted by `day`, `parse`, `part_N` or
+/// `total`. The Markdown can use `{table}`, `{total}`, `{total_parse}`, `{total_part_1}`,
+/// `{total_part_2}`, `{days}`, `{stars}` and `{environment}`, the machine and build the timings
+/// were measured on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableTemplate {
+    columns: Vec<Column>,
+    sort: Column,
+    descending: bool,
+    totals_row: bool,
+    bar_width: usize,
+    body: String,
+}
+
+impl Default for TableTemplate {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Day, Column::Part(1), Column::Part(2)],
+            sort: Column::Day,
+            descending: false,
+            totals_row: false,
+            bar_width: 12,
+            body: "## Benchmarks\n\n{table}\n\n**Total: {total}**\n".into(),
+        }
+    }
+}
+
+impl TableTemplate {
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let mut template = Self::default();
+        let body = match src.strip_prefix("---\n") {
+            Some(rest) => {
+                let (settings, body) = rest
+                    .split_once("\n---\n")
+                    .ok_or("settings are not closed by a `---` line")?;
+                template.configure(&Params::parse(settings)?)?;
+                body
+            }
+            None => src,
+        };
+        template.body = body.to_string();
+        Ok(template)
+    }
+
+    fn configure(&mut self, params: &Params) -> Result<(), String> {
+        if let Some(columns) = params.get::<String>("columns") {
+            self.columns = columns
+                .split(',')
+                .map(|column| column.trim().parse())
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(sort) = params.get::<String>("sort") {
+            self.sort = sort.parse()?;
+            if !matches!(
+                self.sort,
+                Column::Day | Column::Parse | Column::Part(_) | Column::Total
+            ) {
+                return Err(format!("cannot sort by `{sort}`"));
+            }
+        }
+        self.descending = match params.get::<String>("order").as_deref() {
+            None | Some("asc") => false,
+            Some("desc") => true,
+            Some(order) => return Err(format!("unknown order `{order}`")),
+        };
+        self.totals_row = params.get_or("totals_row", self.totals_row);
+        self.bar_width = params.get_or("bar_width", self.bar_width);
+        Ok(())
+    }
+
+    /// Reads `templates/benchmarks.md`, or the default layout if there is none.
+    pub fn read() -> Result<Self, Error> {
+        let path = project_dir().join("templates").join("benchmarks.md");
+        match fs::read_to_string(&path) {
+            Ok(src) => {
+                Self::parse(&src).map_err(|err| Error::Parser(format!("{}: {err}", path.display())))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn render(&self, mut timings: Timings, total_millis: f64) -> String {
+        // days without a value to sort by go last.
+        timings
+            .data
+            .sort_by(|a, b| match (self.sort.nanos(a), self.sort.nanos(b)) {
+                (Some(a), Some(b)) if self.descending => b.cmp(&a),
+                (Some(a), Some(b)) => a.cmp(&b),
+                (a, b) => a.is_none().cmp(&b.is_none()),
+            });
+
+        let slowest = timings
+            .data
+            .iter()
+            .map(|timing| timing.total_nanos)
+            .max()
+            .unwrap_or_default();
+
+        let mut lines: Vec<String> = vec![
+            row(self.columns.iter().map(|column| column.header())),
+            row(self.columns.iter().map(|column| column.alignment().into())),
+        ];
+        for timing in &timings.data {
+            let cells = self
+                .columns
+                .iter()
+                .map(|column| cell(*column, timing, slowest, self.bar_width));
+            lines.push(row(cells));
+        }
+        if self.totals_row {
+            let cells = self.columns.iter().map(|column| match column {
+                Column::Day => "**Total**".into(),
+                Column::Parse | Column::Part(_) | Column::Total => {
+                    format!("`{}`", format_nanos(total_nanos(&timings, *column)))
+                }
+                Column::Stars => count_stars(&timings).to_string(),
+                _ => String::new(),
+            });
+            lines.push(row(cells));
+        }
+        let table = lines.join("\n");
+
+        let body = self
+            .body
+            .replace("{table}", &table)
+            .replace("{total}", &format!("{total_millis:.2}ms"))
+            .replace(
+                "{total_parse}",
+                &format_nanos(total_nanos(&timings, Column::Parse)),
+            )
+            .replace(
+                "{total_part_1}",
+                &format_nanos(total_nanos(&timings, Column::Part(1))),
+            )
+            .replace(
+                "{total_part_2}",
+                &format_nanos(total_nanos(&timings, Column::Part(2))),
+            )
+            .replace("{days}", &timings.data.len().to_string())
+            .replace("{stars}", &count_stars(&timings).to_string())
+            .replace(
+                "{environment}",
+                &timings
+                    .data
+                    .iter()
+                    .find_map(|t| t.environment.as_ref())
+                    .map_or_else(|| "an unknown machine".into(), Environment::describe),
+            );
+
+        format!("{MARKER}\n{}\n{MARKER}", body.trim_end())
+    }
+}
+
+fn row(cells: impl Iterator<Item = String>) -> String {
+    let cells: Vec<String> = cells.collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+fn cell(column: Column, timing: &Timing, slowest: u64, bar_width: usize) -> String {
+    let code = |nanos: Option<u64>| format!("`{}`", nanos.map_or("-".into(), format_nanos));
+    let stats = |part: u8| timing.part(part).copied().unwrap_or_default();
+    match column {
+        Column::Day => format!(
+            "[Day {}]({})",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            get_path_for_bin(timing.day)
+        ),
+        Column::Parse => code(timing.parse.map(|stats| stats.average)),
+        Column::Part(part) => code(timing.part(part).map(|stats| stats.average)),
+        Column::Min(part) => code(stats(part).min),
+        Column::Median(part) => code(stats(part).median),
+        Column::Samples(part) => stats(part)
+            .samples
+            .map_or_else(|| "-".into(), |samples| samples.to_string()),
+        Column::Allocations(part) => stats(part)
+            .allocations
+            .map_or_else(|| "-".into(), |a| a.count.to_string()),
+        Column::Peak(part) => stats(part)
+            .allocations
+            .map_or_else(|| "-".into(), |a| format_bytes(a.peak_bytes)),
+        Column::Total => format!("`{}`", format_nanos(timing.total_nanos)),
+        Column::Status => [1, 2]
+            .map(|part| match timing.status[usize::from(part - 1)] {
+                Some(PartStatus::Failed) => "💥",
+                _ if is_solved(timing, part) => "✔",
+                _ => "✖",
+            })
+            .join(" "),
+        Column::Stars => "⭐".repeat((1..=2).filter(|part| is_solved(timing, *part)).count()),
+        Column::Cost => bar(timing.total_nanos, slowest, bar_width),
     }
+}
+
+fn total_nanos(timings: &Timings, column: Column) -> u64 {
+    timings
+        .data
+        .iter()
+        .filter_map(|timing| column.nanos(timing))
+        .sum()
+}
+
+fn count_stars(timings: &Timings) -> usize {
+    timings
+        .data
+        .iter()
+        .map(|timing| (1..=2).filter(|part| is_solved(timing, *part)).count())
+        .sum()
+}
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+/// A bar of `width` characters for `value` out of `max`, drawn in eighths of a character.
+fn bar(value: u64, max: u64, width: usize) -> String {
+    const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+    if max == 0 || value == 0 {
+        return String::new();
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let eighths = ((u128::from(value) * (width as u128 * 8) + u128::from(max) / 2)
+        / u128::from(max))
+    .max(1) as usize;
+    format!("{}{}", "█".repeat(eighths / 8), EIGHTHS[eighths % 8])
+}
 
-    lines.join("\n")
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    template: &TableTemplate,
+) -> Result<(), Error> {
+    let table = template.render(timings, total_millis);
+    replace_table(s, MARKER, &table)
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+/// Replaces the table between the `marker` comments of `readme` with `table`, which starts and
+/// ends with `marker` itself.
+pub(crate) fn replace_table(readme: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(readme, marker)?;
+    readme.replace_range(positions.pos_start..positions.pos_end, table);
     Ok(())
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let template = TableTemplate::read()?;
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_readme(|readme| update_content(readme, timings, total_millis, &template))
+}
+
+/// Applies `update` to the README of the project.
+pub(crate) fn update_readme(
+    update: impl FnOnce(&mut String) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let path = project_dir().join("README.md");
+    let mut readme = String::from_utf8_lossy(&fs::read(&path)?).to_string();
+    update(&mut readme)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{MARKER, TableTemplate, bar, update_content};
+    use crate::{
+        day,
+        template::allocations::Allocations,
+        template::timings::{PartStatus, Stats, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(Stats::from_average(10_000_000)),
+                    part_2: Some(Stats::from_average(20_000_000)),
+                    total_nanos: 30_000_000_000,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(Stats::from_average(30_000_000)),
+                    part_2: Some(Stats::from_average(40_000_000)),
+                    total_nanos: 70_000_000_000,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1: Some(Stats::from_average(40_000_000)),
+                    part_2: Some(Stats::from_average(50_000_000)),
+                    total_nanos: 90_000_000_000,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 },
             ],
         }
@@ -131,28 +479,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableTemplate::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableTemplate::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableTemplate::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableTemplate::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableTemplate::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +508,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableTemplate::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,10 +516,10 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +528,62 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_custom_tables() {
+        let template = TableTemplate::parse(
+            "---\ncolumns = day, parse, part_1, median_1, allocs_1, peak_1, total, status, stars, cost\nsort = total\norder = desc\ntotals_row = true\nbar_width = 4\n---\n### Timings ({days} days, {stars} stars)\n\n{table}\n",
+        )
+        .unwrap();
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some(Stats::from_average(5_000_000));
+        timings.data[0].part_1 = Some(Stats {
+            average: 10_000_000,
+            min: Some(9_000_000),
+            median: Some(9_500_000),
+            samples: Some(100),
+            allocations: Some(Allocations {
+                count: 3,
+                peak_bytes: 2048,
+            }),
+            counters: None,
+        });
+        timings.data[0].status[0] = Some(PartStatus::Solved);
+        timings.data[1].status[1] = Some(PartStatus::Failed);
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &template).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "### Timings (3 days, 5 stars)",
+            "",
+            "| Day | Parse | Part 1 | Median 1 | Allocs 1 | Peak 1 | Total | Status | Stars | Cost |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :--- |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `-` | - | - | `90.0s` | ✔ ✔ | ⭐⭐ | ████ |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30.0ms` | `-` | - | - | `70.0s` | ✔ 💥 | ⭐ | ███▏ |",
+            "| [Day 1](./src/bin/01.rs) | `5.0ms` | `10.0ms` | `9.5ms` | 3 | 2.0 KiB | `30.0s` | ✔ ✔ | ⭐⭐ | █▍ |",
+            "| **Total** | `5.0ms` | `80.0ms` |  |  |  | `190.0s` |  | 5 |  |",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        assert!(TableTemplate::parse("---\ncolumns = day, speed\n---\n{table}").is_err());
+        assert!(TableTemplate::parse("---\nsort = stars\n---\n{table}").is_err());
+        assert!(TableTemplate::parse("---\ncolumns = day\n{table}").is_err());
+        assert_eq!(
+            TableTemplate::parse("## Benchmarks\n\n{table}\n\n**Total: {total}**\n").unwrap(),
+            TableTemplate::default()
+        );
+    }
+
+    #[test]
+    fn draws_bars_in_eighths() {
+        assert_eq!(bar(1000, 1000, 2), "██");
+        assert_eq!(bar(500, 1000, 3), "█▌");
+        assert_eq!(bar(1, 1000, 2), "▏");
+        assert_eq!(bar(0, 1000, 2), "");
+    }
 }
@@ -0,0 +1,76 @@
+/// Module that maintains the ⭐️ progress table in the readme from locally known answers.
+/// The table has the same format as the one `advent-readme-stars` writes, so either can update it.
+use std::fmt::Write;
+
+use crate::template::answers::Answers;
+use crate::template::aoc_cli;
+use crate::template::readme_benchmarks::{Error, replace_table, update_readme};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+fn render(answers: &Answers, year: u16) -> String {
+    let mut table = format!(
+        "{MARKER}\n## {year} Results\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n"
+    );
+    for (day, [part_1, part_2]) in answers.stars() {
+        let star = |solved: bool| if solved { "⭐" } else { " " };
+        let n = day.into_inner();
+        writeln!(
+            table,
+            "| [Day {n}](https://adventofcode.com/{year}/day/{n}) | {} | {} |",
+            star(part_1),
+            star(part_2)
+        )
+        .unwrap();
+    }
+    table.push_str(MARKER);
+    table
+}
+
+fn update_content(s: &mut String, answers: &Answers, year: u16) -> Result<(), Error> {
+    replace_table(s, MARKER, &render(answers, year))
+}
+
+pub fn update(answers: &Answers) -> Result<(), Error> {
+    let year = aoc_cli::year();
+    update_readme(|readme| update_content(readme, answers, year))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MARKER, update_content};
+    use crate::day;
+    use crate::template::answers::{Answers, Submission, Verdict};
+
+    #[test]
+    fn updates_star_table() {
+        let mut answers = Answers::default();
+        for (day, part, verdict) in [
+            (day!(1), 1, Verdict::Correct),
+            (day!(1), 2, Verdict::Correct),
+            (day!(2), 1, Verdict::Correct),
+            (day!(3), 1, Verdict::Incorrect),
+        ] {
+            answers.record(Submission {
+                day,
+                part,
+                answer: "1".into(),
+                verdict,
+            });
+        }
+
+        let mut s = format!("foo\n{MARKER}\nbar\n{MARKER}\nbaz");
+        update_content(&mut s, &answers, 2025).unwrap();
+        let expected = format!(
+            "foo\n{MARKER}\n## 2025 Results\n\n\
+            | Day | Part 1 | Part 2 |\n\
+            | :---: | :---: | :---: |\n\
+            | [Day 1](https://adventofcode.com/2025/day/1) | ⭐ | ⭐ |\n\
+            | [Day 2](https://adventofcode.com/2025/day/2) | ⭐ |   |\n\
+            {MARKER}\nbaz"
+        );
+        assert_eq!(s, expected);
+    }
+}
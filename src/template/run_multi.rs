@@ -1,40 +1,126 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, project_dir};
 
 use super::{
-    all_days,
+    all_days, allocations,
+    environment::Environment,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// The result of running a set of days.
+pub struct RunSummary {
+    /// Benchmark timings, only collected when running timed.
+    pub timings: Option<Timings>,
+    /// Days that failed, with the stages that failed (e.g. `Part 2`) or an empty list if the
+    /// binary exited unsuccessfully without reporting a failed stage.
+    pub failures: Vec<(Day, Vec<String>)>,
+}
+
+/// How `run_multi` runs each day.
+#[derive(Clone, Copy, Debug)]
+pub struct RunOptions<'a> {
+    /// Cargo profile the days are built with, a debug build if there is none.
+    pub profile: Option<&'a str>,
+    /// Whether the stages of each day are benched.
+    pub is_timed: bool,
+    /// Whether timed runs also read the hardware counters of each stage.
+    pub counters: bool,
+    /// How many days run at the same time.
+    pub jobs: usize,
+    /// CPU the solutions are pinned to.
+    pub pin_cpu: Option<usize>,
+    /// The only part that runs, both if there is none.
+    pub part: Option<u8>,
+    /// Whether the days are built with the `allocations` feature, which counts what each stage
+    /// allocates.
+    pub allocations: bool,
+}
+
+impl Default for RunOptions<'_> {
+    fn default() -> Self {
+        Self {
+            profile: None,
+            is_timed: false,
+            counters: false,
+            jobs: 1,
+            pin_cpu: None,
+            part: None,
+            allocations: false,
+        }
+    }
+}
+
+impl RunOptions<'_> {
+    /// The machine and build the days run on, `dev` being the profile of a build without one.
+    pub(crate) fn environment(&self) -> Environment {
+        Environment::current(
+            self.profile.unwrap_or("dev"),
+            allocations::allocator_name(self.allocations),
+        )
+    }
+}
+
+/// Runs `days_to_run` in order. With more than one job, days run at the same time and the
+/// output of each day is printed at once, in order, when it finished.
+pub fn run_multi(days_to_run: &HashSet<Day>, options: RunOptions) -> RunSummary {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failures = vec![];
 
     let mut need_space = false;
+    let mut print_header = |day: Day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut finish = |day: Day, output: &child_commands::Output| {
+        let failed_stages = child_commands::parse_failed_stages(&output.stdout);
+        if !output.success || !failed_stages.is_empty() {
+            failures.push((day, failed_stages));
+        }
 
-            if output.is_empty() {
+        if output.stdout.is_empty() {
+            if output.success {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
             }
+        } else {
+            let val = child_commands::parse_exec_time(&output.stdout, day);
+            timings.push(val);
+        }
+    };
+
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    if options.jobs <= 1 {
+        for day in days {
+            print_header(day);
+            let output = child_commands::run_solution(day, &options, true).unwrap();
+            finish(day, &output);
+        }
+    } else {
+        run_parallel(&days, &options, |day, output| {
+            print_header(day);
+            output.print();
+            finish(day, &output);
         });
+    }
 
-    if is_timed {
+    let timings = if options.is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,7 +129,60 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    if !failures.is_empty() {
+        let failed: Vec<String> = failures
+            .iter()
+            .map(|(day, stages)| {
+                if stages.is_empty() {
+                    format!("Day {day}")
+                } else {
+                    format!("Day {day} ({})", stages.join(", "))
+                }
+            })
+            .collect();
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET} {}", failed.join(", "));
     }
+
+    RunSummary { timings, failures }
+}
+
+/// Runs `days` on `options.jobs` threads, passing the buffered output of each day to `finish` in
+/// the order of `days`, as soon as the days before it finished.
+fn run_parallel(
+    days: &[Day],
+    options: &RunOptions,
+    mut finish: impl FnMut(Day, child_commands::Output),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(days.len()) {
+            let (next, sender) = (&next, sender.clone());
+            scope.spawn(move || {
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let output = child_commands::run_solution(*day, options, false).unwrap();
+                    if sender.send((*day, output)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished = BTreeMap::new();
+        let mut remaining = days.iter();
+        let mut upcoming = remaining.next();
+        for (day, output) in receiver {
+            finished.insert(day, output);
+            while let Some(output) = upcoming.and_then(|day| finished.remove(day)) {
+                finish(*upcoming.unwrap(), output);
+                upcoming = remaining.next();
+            }
+        }
+    });
 }
 
 #[allow(dead_code)]
@@ -59,41 +198,95 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// Path of the solution of `day`, resolved from the [`project_dir`].
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(day: Day) -> PathBuf {
+    project_dir()
+        .join("src")
+        .join("bin")
+        .join(format!("{day}.rs"))
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{Error, RunOptions, get_path_for_bin};
+    use crate::template::allocations::Allocations;
+    use crate::template::counters::Counters;
+    use crate::template::runner::{COUNTERS_PREFIX, TIMING_PREFIX};
+    use crate::template::timings::{PartStatus, Stats};
+    use crate::template::{Day, project_dir};
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// What the solution bin of a day printed and whether it exited successfully.
+    pub struct Output {
+        pub stdout: Vec<String>,
+        /// Only kept when the output was not forwarded as it was printed.
+        pub stderr: Vec<String>,
+        pub success: bool,
+    }
+
+    impl Output {
+        /// Prints buffered output, without the lines only meant for `cargo time`.
+        pub fn print(&self) {
+            for line in self.stdout.iter().filter(|line| !is_report(line)) {
+                println!("{line}");
+            }
+            for line in &self.stderr {
+                eprintln!("{line}");
+            }
+        }
+    }
+
+    fn is_report(line: &str) -> bool {
+        line.starts_with(TIMING_PREFIX) || line.starts_with(COUNTERS_PREFIX)
+    }
+
+    /// Run the solution bin for a given day. With `forward`, its output is printed while it runs,
+    /// otherwise it is only buffered.
+    pub fn run_solution(day: Day, options: &RunOptions, forward: bool) -> Result<Output, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !get_path_for_bin(day).exists() {
+            return Ok(Output {
+                stdout: vec![],
+                stderr: vec![],
+                success: true,
+            });
         }
 
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
-            args.push("--release");
+        if let Some(profile) = options.profile {
+            args.push("--profile");
+            args.push(profile);
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
+        if options.allocations {
+            args.extend(["--features", "allocations"]);
+        }
+
+        // mirror flags to child invocations.
+        let mut bin_args = vec![];
+        if options.is_timed {
+            bin_args.push("--time".to_string());
+            if options.counters {
+                bin_args.push("--counters".to_string());
+            }
+        }
+        if let Some(part) = options.part {
+            bin_args.extend(["--part".to_string(), part.to_string()]);
+        }
+        if let Some(cpu) = options.pin_cpu {
+            bin_args.extend(["--pin-cpu".to_string(), cpu.to_string()]);
+        }
+        if !bin_args.is_empty() {
             args.push("--");
-            args.push("--time");
+            args.extend(bin_args.iter().map(String::as_str));
         }
 
         // spawn child command with piped stdout/stderr.
@@ -101,6 +294,7 @@ pub mod child_commands {
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .current_dir(project_dir())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -111,21 +305,47 @@ pub mod child_commands {
         let mut output = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            let mut lines = vec![];
+            for line in stderr.lines() {
+                let line = line.unwrap();
+                if forward {
+                    eprintln!("{line}");
+                } else {
+                    lines.push(line);
+                }
+            }
+            lines
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            if forward && !is_report(&line) {
+                println!("{line}");
+            }
             output.push(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let stderr = thread.join().unwrap();
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(Output {
+            stdout: output,
+            stderr,
+            success: status.success(),
+        })
+    }
+
+    /// Stages (`Parse`, `Part 1`, `Part 2`) reported as failed by the runner.
+    pub fn parse_failed_stages(output: &[String]) -> Vec<String> {
+        output
+            .iter()
+            .filter_map(|l| {
+                // the runner overwrites intermediate results with `\r`, keep what was shown last.
+                let l = l.rsplit('\r').next().unwrap_or(l);
+                l.split_once(": ✖ failed:")
+                    .map(|(stage, _)| stage.to_string())
+            })
+            .collect()
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -133,79 +353,127 @@ pub mod child_commands {
             day,
             part_1: None,
             part_2: None,
-            total_nanos: 0_f64,
+            total_nanos: 0,
+            parse: None,
+            status: [None, None],
+            environment: None,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+        for line in output {
+            if let Some(timing) = line.strip_prefix(TIMING_PREFIX) {
+                let Some((stage, stats)) = parse_timing(timing) else {
+                    eprintln!("Could not parse timings from line: {line}");
+                    continue;
+                };
+                timings.total_nanos += stats.average;
+                match stage {
+                    "parse" => timings.parse = Some(stats),
+                    "part_1" => {
+                        timings.part_1 = Some(stats);
+                        timings.status[0] = Some(PartStatus::Solved);
+                    }
+                    "part_2" => {
+                        timings.part_2 = Some(stats);
+                        timings.status[1] = Some(PartStatus::Solved);
+                    }
+                    _ => {}
                 }
+                continue;
+            }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+            if let Some(counters) = line.strip_prefix(COUNTERS_PREFIX) {
+                let Some((stage, counters)) = parse_counters(counters) else {
+                    eprintln!("Could not parse counters from line: {line}");
+                    continue;
                 };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                let stats = match stage {
+                    "parse" => timings.parse.as_mut(),
+                    "part_1" => timings.part_1.as_mut(),
+                    "part_2" => timings.part_2.as_mut(),
+                    _ => None,
+                };
+                if let Some(stats) = stats {
+                    stats.counters = Some(counters);
                 }
+                continue;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            // the runner overwrites intermediate results with `\r`, keep what was shown last.
+            let line = line.rsplit('\r').next().unwrap_or(line);
+            let (part, rest) = match line.split_once(':') {
+                Some(("Part 1", rest)) => (0, rest),
+                Some(("Part 2", rest)) => (1, rest),
+                _ => continue,
+            };
+            if rest.starts_with(" ✖") {
+                timings.status[part] = Some(if rest.starts_with(" ✖ failed:") {
+                    PartStatus::Failed
+                } else {
+                    PartStatus::Unsolved
+                });
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Reads what the runner reports for a benched stage, e.g. `part_1 74130 70012 72301 1000`
+    /// for the average, min, median and number of samples in nanoseconds, optionally followed by
+    /// the number of allocations and peak bytes.
+    fn parse_timing(timing: &str) -> Option<(&str, Stats)> {
+        let mut fields = timing.split_whitespace();
+        let stage = fields.next()?;
+        let numbers: Vec<u64> = fields
+            .map(|field| field.parse().ok())
+            .collect::<Option<_>>()?;
+        let (average, min, median, samples, allocations) = match numbers[..] {
+            [average, min, median, samples] => (average, min, median, samples, None),
+            [average, min, median, samples, count, peak_bytes] => (
+                average,
+                min,
+                median,
+                samples,
+                Some(Allocations { count, peak_bytes }),
+            ),
+            _ => return None,
+        };
+        let stats = Stats {
+            average,
+            min: Some(min),
+            median: Some(median),
+            samples: Some(samples),
+            allocations,
+            counters: None,
+        };
+        Some((stage, stats))
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Reads the hardware counters the runner reports for a stage, e.g. `part_1 1200 400 3 0` for
+    /// the instructions, cycles, branch misses and cache misses of a run.
+    fn parse_counters(counters: &str) -> Option<(&str, Counters)> {
+        let mut fields = counters.split_whitespace();
+        let stage = fields.next()?;
+        let numbers: Vec<u64> = fields
+            .map(|field| field.parse().ok())
+            .collect::<Option<_>>()?;
+        let [instructions, cycles, branch_misses, cache_misses] = numbers[..] else {
+            return None;
+        };
+        let counters = Counters {
+            instructions,
+            cycles,
+            branch_misses,
+            cache_misses,
+        };
+        Some((stage, counters))
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
         use super::parse_exec_time;
+        use crate::template::allocations::Allocations;
+        use crate::template::counters::Counters;
+        use crate::template::timings::{PartStatus, Stats};
 
         use crate::day;
 
@@ -213,30 +481,91 @@ pub mod child_commands {
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "Part 1: 0 (74.1ns @ 100000 samples, min 70.0ns, median 72.0ns, 2 allocs, peak 64 B)".into(),
+                    "  1.2K instructions, 400 cycles, IPC 3.00, 2 branch misses, 0 cache misses"
+                        .into(),
+                    "@timing part_1 74 70 72 100000 2 64".into(),
+                    "@counters part_1 1200 400 2 0".into(),
+                    "Part 2: 10 (74.1ms @ 67 samples, min 70.0ms, median 72.0ms)".into(),
+                    "@timing part_2 74130000 70000000 72000000 67".into(),
                     "".into(),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74_130_074);
+            assert_eq!(
+                res.part_1,
+                Some(Stats {
+                    average: 74,
+                    min: Some(70),
+                    median: Some(72),
+                    samples: Some(100_000),
+                    allocations: Some(Allocations {
+                        count: 2,
+                        peak_bytes: 64,
+                    }),
+                    counters: Some(Counters {
+                        instructions: 1200,
+                        cycles: 400,
+                        branch_misses: 2,
+                        cache_misses: 0,
+                    }),
+                })
+            );
+            assert_eq!(res.part_2.unwrap().average, 74_130_000);
+            assert_eq!(res.part_2.unwrap().allocations, None);
+            assert_eq!(res.status, [Some(PartStatus::Solved); 2]);
         }
 
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
+                    "Part 1: @timing part_2 1 1 1 1 (2.0s @ 5 samples, min 2.0s, median 2.0s)"
+                        .into(),
+                    "@timing part_1 2000000000 2000000000 2000000000 5".into(),
                     "".into(),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 2_000_000_000);
+            assert_eq!(res.part_1.unwrap().average, 2_000_000_000);
+            assert_eq!(res.part_2, None);
+        }
+
+        #[test]
+        fn parses_stats_and_statuses() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: > benching\rParse: (1.5ms @ 20 samples, min 1.2ms, median 1.4ms)"
+                        .into(),
+                    "@timing parse 1500000 1200000 1400000 20".into(),
+                    "Part 1: 42 (10.0µs @ 500 samples, min 9.0µs, median 9.8µs)".into(),
+                    "@timing part_1 10000 9000 9800 500".into(),
+                    "Part 2: ✖ failed: panicked: index out of bounds".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos, 1_510_000);
+            assert_eq!(res.parse.unwrap().average, 1_500_000);
+            assert_eq!(res.part_1.unwrap().median, Some(9800));
+            assert_eq!(res.part_2, None);
+            assert_eq!(
+                res.status,
+                [Some(PartStatus::Solved), Some(PartStatus::Failed)]
+            );
+        }
+
+        #[test]
+        fn parses_failed_stages() {
+            let res = super::parse_failed_stages(&[
+                "Part 1: ✖\rPart 1: ✖ failed: panicked: index out of bounds".into(),
+                "Part 2: ✖\rPart 2: ✖             ".into(),
+                "Part 2: \u{1b}[1m10\u{1b}[0m\rPart 2: \u{1b}[1m10\u{1b}[0m (100ms)".into(),
+                "Parse:\rParse: ✖ failed: bad input".into(),
+                "  caused by: invalid digit found in string".into(),
+            ]);
+            assert_eq!(res, ["Part 1", "Parse"]);
         }
 
         #[test]
@@ -249,9 +578,10 @@ pub mod child_commands {
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.total_nanos, 0);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.status, [Some(PartStatus::Unsolved); 2]);
         }
     }
 }
@@ -2,61 +2,230 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::allocations::{self, Allocations, format_bytes};
+use crate::template::answers::{Answers, Submission, Verdict};
+use crate::template::counters::{self, Counters};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Answer, Day, Outcome, Solution, aoc_cli};
+use crate::template::{cpu_profile, readme_stars};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Validates and parses the input once, timing the parse stage if the solution has one, and runs
+/// both parts. Panics are caught per stage, so part 2 still runs when part 1 fails.
+/// Returns `false` if any stage failed.
+pub fn run_solution<S: Solution>(solution: &S, input: &str, day: Day) -> bool {
+    pin_cpu();
+
+    if let Err(err) = solution.validate(input) {
+        println!("Input: ✖ invalid: {err}");
+        return false;
+    }
+
+    let parsed = if S::HAS_PARSE_STAGE {
+        let (parsed, timed) = run_timed(
+            |input| catch_panic(|| solution.parse(input)),
+            input,
+            |parsed| {
+                print!("Parse:");
+                parsed.is_ok()
+            },
+        );
+        print!("\r");
+        match parsed {
+            Ok(parsed) => {
+                println!("Parse:{}", format_duration(&timed));
+                print_counters(&timed);
+                report_timing("parse", &timed);
+                parsed
+            }
+            Err(message) => {
+                println!("Parse: ✖ failed: {message}");
+                return false;
+            }
+        }
+    } else {
+        solution.parse(input)
+    };
+
+    let only_part = only_part();
+    let part_one =
+        only_part == Some(2) || run_part(|parsed| solution.part_one(parsed), &parsed, day, 1);
+    let part_two =
+        only_part == Some(1) || run_part(|parsed| solution.part_two(parsed), &parsed, day, 2);
+    part_one && part_two
+}
+
+/// The part passed with `--part <part>`, if only one part should run.
+fn only_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--part")?;
+    match args.get(index + 1).map(|part| part.parse()) {
+        Some(Ok(part @ (1 | 2))) => Some(part),
+        _ => {
+            eprintln!("Part must be 1 or 2");
+            process::exit(1);
+        }
+    }
+}
+
+/// Pins the process to the CPU passed with `--pin-cpu <cpu>`, so the scheduler does not move the
+/// solution between cores while it is benched.
+fn pin_cpu() {
+    let args: Vec<String> = env::args().collect();
+    let Some(index) = args.iter().position(|x| x == "--pin-cpu") else {
+        return;
+    };
+    let Some(cpu) = args.get(index + 1).and_then(|cpu| cpu.parse().ok()) else {
+        eprintln!("Unexpected command-line input. Format: --pin-cpu <cpu>");
+        process::exit(1);
+    };
+    if let Err(e) = pin_to(cpu) {
+        eprintln!("Failed to pin to CPU {cpu}: {e}");
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn pin_to(cpu: usize) -> Result<(), String> {
+    if cpu >= libc::CPU_SETSIZE as usize {
+        return Err("no such CPU".into());
+    }
+    // SAFETY: an all-zero `cpu_set_t` is an empty set, and `cpu` is within its bounds.
+    let result = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(cpu, &mut set);
+        libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), &raw const set)
+    };
+    if result == -1 {
+        Err(std::io::Error::last_os_error().to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_to(_cpu: usize) -> Result<(), String> {
+    Err("pinning is only supported on Linux".into())
+}
+
+/// Runs a single part and prints its outcome. Returns `false` if the part failed.
+pub fn run_part<I: Copy, A: Answer>(func: impl Fn(I) -> A, input: I, day: Day, part: u8) -> bool {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, timed) = run_timed(
+        |input| catch_panic(|| func(input).into_outcome()).unwrap_or_else(Outcome::Failed),
+        input,
+        |result| {
+            print_result(result, &part_str, "");
+            !matches!(result, Outcome::Failed(_))
+        },
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_duration(&timed));
+    print_counters(&timed);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if matches!(result, Outcome::Solved(_)) && env::args().any(|x| x == "--profile-cpu") {
+        match cpu_profile::profile(&func, input, day, part) {
+            Ok((path, runs)) => println!(
+                "Profiled {part_str} over {runs} runs: \"{}\"",
+                path.display()
+            ),
+            Err(e) => eprintln!("Failed to profile {part_str}: {e}"),
+        }
+    }
+
+    match result {
+        Outcome::Solved(result) => {
+            report_timing(&format!("part_{part}"), &timed);
+            submit_result(result, day, part);
+            true
+        }
+        Outcome::Unsolved => true,
+        Outcome::Failed(_) => false,
+    }
+}
+
+/// Runs `func`, turning a panic into an error carrying the panic message.
+pub(crate) fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(func)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic payload".into());
+        format!("panicked: {message}")
+    })
+}
+
+/// How long a stage took. Benched stages report statistics over all samples, stages that ran
+/// once report that run for all of them.
+struct Timed {
+    average: Duration,
+    min: Duration,
+    median: Duration,
+    samples: u128,
+    benched: bool,
+    /// What the first run allocated, unknown unless [`CountingAlloc`] is the global allocator.
+    ///
+    /// [`CountingAlloc`]: allocations::CountingAlloc
+    allocations: Option<Allocations>,
+    /// Hardware counters of a run, if they were asked for with `--counters`.
+    counters: Option<Result<Counters, String>>,
+}
+
+impl Timed {
+    fn once(duration: Duration) -> Self {
+        Self {
+            average: duration,
+            min: duration,
+            median: duration,
+            samples: 1,
+            benched: false,
+            allocations: None,
+            counters: None,
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+///
+/// The hook receives the result of the first execution and returns whether it is worth benching.
+fn run_timed<I: Copy, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T) -> bool) -> (T, Timed) {
     let timer = Instant::now();
-    let result = {
+    let (result, allocations) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        allocations::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let should_bench = hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let mut timed = if should_bench && std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Timed::once(base_time)
     };
+    timed.allocations = allocations;
 
-    (result, run.0, run.1)
+    (result, timed)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Timed {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = Duration::from_secs(5).as_nanos() / cmp::max(base_time.as_nanos(), 10);
+    // stages slower than the budget still run once, so they are reported as benched.
+    let bench_iterations =
+        (Duration::from_secs(5).as_nanos() / cmp::max(base_time.as_nanos(), 10)).max(1);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -66,11 +235,29 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // counted apart from the timed runs, so reading the clock is not counted. A fifth of the
+    // samples is plenty for stable counts.
+    let counters = env::args().any(|x| x == "--counters").then(|| {
+        let runs = u64::try_from(bench_iterations / 5)
+            .unwrap_or(u64::MAX)
+            .max(1);
+        counters::count(runs, || {
+            black_box(func(black_box(input)));
+        })
+    });
+
+    #[allow(clippy::cast_possible_truncation)]
+    let average = Duration::from_nanos(average_duration(&timers) as u64);
+    timers.sort_unstable();
+    Timed {
+        average,
+        min: timers[0],
+        median: timers[timers.len() / 2],
+        samples: bench_iterations,
+        benched: true,
+        allocations: None,
+        counters,
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -81,19 +268,73 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_duration(timed: &Timed) -> String {
+    let Timed {
+        average,
+        min,
+        median,
+        samples,
+        benched,
+        allocations,
+        ..
+    } = timed;
+    let allocations = allocations.map_or(String::new(), |a| {
+        format!(", {} allocs, peak {}", a.count, format_bytes(a.peak_bytes))
+    });
+    if !benched {
+        format!(" ({average:.1?}{allocations})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({average:.1?} @ {samples} samples, min {min:.1?}, median {median:.1?}{allocations})"
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Prefix of the lines benched stages are reported on for `cargo time`, which reads them instead
+/// of the formatted durations.
+pub(crate) const TIMING_PREFIX: &str = "@timing ";
+
+/// Prefix of the lines the hardware counters of a stage are reported on for `cargo time`.
+pub(crate) const COUNTERS_PREFIX: &str = "@counters ";
+
+/// Reports a benched `stage` in nanoseconds, e.g. `@timing part_1 74130 70012 72301 1000` for
+/// the average, min, median and number of samples, followed by the number of allocations and
+/// peak bytes if they were counted.
+fn report_timing(stage: &str, timed: &Timed) {
+    if timed.benched {
+        let allocations = timed
+            .allocations
+            .map_or(String::new(), |a| format!(" {} {}", a.count, a.peak_bytes));
+        println!(
+            "{TIMING_PREFIX}{stage} {} {} {} {}{allocations}",
+            timed.average.as_nanos(),
+            timed.min.as_nanos(),
+            timed.median.as_nanos(),
+            timed.samples
+        );
+    }
+    if let Some(Ok(counters)) = timed.counters {
+        println!(
+            "{COUNTERS_PREFIX}{stage} {} {} {} {}",
+            counters.instructions, counters.cycles, counters.branch_misses, counters.cache_misses
+        );
+    }
+}
+
+/// Prints the hardware counters of a stage below its time, or why they could not be read.
+fn print_counters(timed: &Timed) {
+    match &timed.counters {
+        Some(Ok(counters)) => println!("  {}", counters.describe()),
+        Some(Err(e)) => println!("  Hardware counters unavailable: {e}"),
+        None => {}
+    }
+}
+
+fn print_result<T: Display>(result: &Outcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Outcome::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -113,7 +354,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -121,12 +362,23 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(message) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ failed: {message}");
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///  3. the answer has not been judged before.
+///
+/// The verdict is recorded in `data/answers.json` and the ⭐️ table in the readme is updated.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
@@ -161,6 +413,37 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    let answer = result.to_string();
+    let mut answers = Answers::read_from_file();
+    match answers.verdict(day, part, &answer) {
+        Some(Verdict::Correct) => {
+            println!("Not submitting {answer}, it was already accepted.");
+            return None;
+        }
+        Some(Verdict::Incorrect) => {
+            println!("Not submitting {answer}, it was already judged wrong.");
+            return None;
+        }
+        None => {}
+    }
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let output = aoc_cli::submit(day, part, &answer);
+    if let Ok(output) = &output {
+        let response = [&output.stdout[..], &output.stderr[..]].concat();
+        if let Some(verdict) = Verdict::from_response(&String::from_utf8_lossy(&response)) {
+            answers.record(Submission {
+                day,
+                part,
+                answer,
+                verdict,
+            });
+            if let Err(e) = answers.store_file() {
+                eprintln!("Failed to store answers: {e}");
+            } else if let Err(e) = readme_stars::update(&answers) {
+                eprintln!("Failed to update the readme: {e:?}");
+            }
+        }
+    }
+    Some(output)
 }
@@ -0,0 +1,102 @@
+//! Which days `cargo all` and `cargo time` run.
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::time::UNIX_EPOCH;
+
+use crate::template::environment::comparable;
+use crate::template::run_multi::{RunOptions, get_path_for_bin};
+use crate::template::timings::{PartStatus, Timings};
+use crate::template::{Day, DaySet};
+
+/// Days given on the command line, narrowed down by filters that look at the timings stored by
+/// `cargo time --store`. Only timings of the current machine and build are considered.
+#[derive(Clone, Debug, Default)]
+pub struct DaySelection {
+    /// Days to pick from, otherwise the days the command runs by default.
+    pub days: Option<DaySet>,
+    /// Only days with a part that failed when they were last timed.
+    pub only_failing: bool,
+    /// Only days whose `src/bin/NN.rs` changed since their timing was last stored, including
+    /// days that were never stored.
+    pub only_changed: bool,
+    /// Only the `K` days with the slowest stored total.
+    pub slowest: Option<usize>,
+}
+
+impl DaySelection {
+    /// Whether the days are narrowed down by stored timings.
+    pub fn has_filters(&self) -> bool {
+        self.only_failing || self.only_changed || self.slowest.is_some()
+    }
+
+    /// The days to run: the given days or `default` if there are none, with the filters applied.
+    /// Only stored timings of the build the days run in with `options` are considered.
+    pub fn resolve(
+        &self,
+        default: impl Iterator<Item = Day>,
+        options: &RunOptions,
+    ) -> HashSet<Day> {
+        let mut days: Vec<Day> = match &self.days {
+            Some(set) => set.days().collect(),
+            None => default.collect(),
+        };
+        if !self.has_filters() {
+            return days.into_iter().collect();
+        }
+
+        let environment = options.environment();
+        let timings = Timings::read_from_file().for_environment(Some(&environment));
+
+        if self.only_failing {
+            days.retain(|day| {
+                timings
+                    .data
+                    .iter()
+                    .any(|t| t.day == *day && t.status.contains(&Some(PartStatus::Failed)))
+            });
+        }
+
+        if self.only_changed {
+            // seconds since the unix epoch at which each day was last stored.
+            let mut stored_at: HashMap<Day, u64> = HashMap::new();
+            for recorded in Timings::read_history() {
+                if comparable(recorded.timing.environment.as_ref(), Some(&environment)) {
+                    stored_at.insert(recorded.timing.day, recorded.recorded_at);
+                }
+            }
+            days.retain(|day| {
+                let Some(modified) = modified_at(*day) else {
+                    return false;
+                };
+                stored_at.get(day).is_none_or(|stored| modified >= *stored)
+            });
+        }
+
+        if let Some(count) = self.slowest {
+            let total = |day: &Day| {
+                timings
+                    .data
+                    .iter()
+                    .filter(|t| t.day == *day)
+                    .map(|t| t.total_nanos)
+                    .max()
+            };
+            days.retain(|day| total(day).is_some());
+            days.sort_by_key(|day| std::cmp::Reverse(total(day)));
+            days.truncate(count);
+        }
+
+        days.into_iter().collect()
+    }
+}
+
+/// Seconds since the unix epoch at which the solution of `day` was last modified, `None` if it
+/// has not been scaffolded.
+fn modified_at(day: Day) -> Option<u64> {
+    let modified = fs::metadata(get_path_for_bin(day)).ok()?.modified().ok()?;
+    Some(
+        modified
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+    )
+}
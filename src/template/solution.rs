@@ -0,0 +1,276 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io;
+use std::marker::PhantomData;
+
+use crate::template::{InputError, Params};
+
+/// What a part returns: [`Option`] for parts that may be unsolved, or [`Result`] for parts that can
+/// fail with an error.
+pub trait Answer {
+    type Value: Display;
+
+    fn into_outcome(self) -> Outcome<Self::Value>;
+}
+
+/// How a part ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<T> {
+    Solved(T),
+    Unsolved,
+    /// The part returned an error or panicked, with the message of the error and its sources.
+    Failed(String),
+}
+
+impl<T: Display> Answer for Option<T> {
+    type Value = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Some(answer) => Outcome::Solved(answer),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> Answer for Result<T, E> {
+    type Value = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Ok(answer) => Outcome::Solved(answer),
+            Err(err) => Outcome::Failed(format_error_chain(&*err.into())),
+        }
+    }
+}
+
+/// Formats an error followed by one `caused by` line per source.
+pub fn format_error_chain(err: &dyn Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(&format!("\n  caused by: {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+/// A day's solution. Both parts are computed from the same parsed input, which is parsed once.
+///
+/// Days that work on the raw input can implement [`RawSolution`] instead, and days written as
+/// free functions are adapted with [`FreeFunctions`].
+pub trait Solution {
+    /// The parsed input, which may borrow from the raw input.
+    type Parsed<'a>;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    /// Whether [`Solution::parse`] does any work, in which case the runner times it separately.
+    const HAS_PARSE_STAGE: bool = true;
+
+    /// Applies the parameters of the input about to be solved, see [`Params`]. Called before
+    /// [`Solution::parse`], so solutions whose parts depend on the input hold them as fields.
+    fn configure(&mut self, _params: &Params) {}
+
+    /// [`Solution::configure`] for a solution built in place, like in tests.
+    #[must_use]
+    fn with_params(mut self, params: &Params) -> Self
+    where
+        Self: Sized,
+    {
+        self.configure(params);
+        self
+    }
+
+    /// Checks that the input has the expected shape before it is parsed, so a malformed input is
+    /// reported with its line instead of a panic deep inside the solution. Inputs are already
+    /// normalized, see [`normalize_input`](crate::template::normalize_input).
+    fn validate(&self, _input: &str) -> Result<(), InputError> {
+        Ok(())
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Self::Parsed<'a>;
+
+    fn part_one(&self, parsed: &Self::Parsed<'_>) -> Self::Answer1;
+
+    fn part_two(&self, parsed: &Self::Parsed<'_>) -> Self::Answer2;
+
+    /// Interactive visualization of part one, started with `cargo tui`.
+    fn part_one_tui(&self, _input: &str) -> io::Result<()> {
+        Err(no_visualization(1))
+    }
+
+    /// Interactive visualization of part two, started with `cargo tui`.
+    fn part_two_tui(&self, _input: &str) -> io::Result<()> {
+        Err(no_visualization(2))
+    }
+}
+
+/// A [`Solution`] without a parse stage, both parts receive the raw input.
+pub trait RawSolution {
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn configure(&mut self, _params: &Params) {}
+
+    fn validate(&self, _input: &str) -> Result<(), InputError> {
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> Self::Answer1;
+
+    fn part_two(&self, input: &str) -> Self::Answer2;
+
+    fn part_one_tui(&self, _input: &str) -> io::Result<()> {
+        Err(no_visualization(1))
+    }
+
+    fn part_two_tui(&self, _input: &str) -> io::Result<()> {
+        Err(no_visualization(2))
+    }
+}
+
+impl<T: RawSolution> Solution for T {
+    type Parsed<'a> = &'a str;
+    type Answer1 = T::Answer1;
+    type Answer2 = T::Answer2;
+
+    const HAS_PARSE_STAGE: bool = false;
+
+    fn configure(&mut self, params: &Params) {
+        RawSolution::configure(self, params);
+    }
+
+    fn validate(&self, input: &str) -> Result<(), InputError> {
+        RawSolution::validate(self, input)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> &'a str {
+        input
+    }
+
+    fn part_one(&self, parsed: &&str) -> Self::Answer1 {
+        RawSolution::part_one(self, parsed)
+    }
+
+    fn part_two(&self, parsed: &&str) -> Self::Answer2 {
+        RawSolution::part_two(self, parsed)
+    }
+
+    fn part_one_tui(&self, input: &str) -> io::Result<()> {
+        RawSolution::part_one_tui(self, input)
+    }
+
+    fn part_two_tui(&self, input: &str) -> io::Result<()> {
+        RawSolution::part_two_tui(self, input)
+    }
+}
+
+fn no_visualization(part: u8) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("part {part} has no visualization"),
+    )
+}
+
+/// Adapts the `part_one`, `part_one_tui`, `part_two` and `part_two_tui` functions of a day to
+/// [`RawSolution`]. This is what [`solution!`](crate::solution) uses when given only a day.
+pub struct FreeFunctions<A1, A2, P1, T1, P2, T2> {
+    part_one: P1,
+    part_one_tui: T1,
+    part_two: P2,
+    part_two_tui: T2,
+    answers: PhantomData<fn() -> (A1, A2)>,
+}
+
+impl<A1, A2, P1, T1, P2, T2> FreeFunctions<A1, A2, P1, T1, P2, T2>
+where
+    P1: Fn(&str) -> A1,
+    T1: Fn(&str) -> io::Result<()>,
+    P2: Fn(&str) -> A2,
+    T2: Fn(&str) -> io::Result<()>,
+{
+    pub fn new(part_one: P1, part_one_tui: T1, part_two: P2, part_two_tui: T2) -> Self {
+        Self {
+            part_one,
+            part_one_tui,
+            part_two,
+            part_two_tui,
+            answers: PhantomData,
+        }
+    }
+}
+
+impl<A1, A2, P1, T1, P2, T2> RawSolution for FreeFunctions<A1, A2, P1, T1, P2, T2>
+where
+    A1: Answer,
+    A2: Answer,
+    P1: Fn(&str) -> A1,
+    T1: Fn(&str) -> io::Result<()>,
+    P2: Fn(&str) -> A2,
+    T2: Fn(&str) -> io::Result<()>,
+{
+    type Answer1 = A1;
+    type Answer2 = A2;
+
+    fn part_one(&self, input: &str) -> A1 {
+        (self.part_one)(input)
+    }
+
+    fn part_two(&self, input: &str) -> A2 {
+        (self.part_two)(input)
+    }
+
+    fn part_one_tui(&self, input: &str) -> io::Result<()> {
+        (self.part_one_tui)(input)
+    }
+
+    fn part_two_tui(&self, input: &str) -> io::Result<()> {
+        (self.part_two_tui)(input)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, Outcome};
+    use std::{error::Error, fmt::Display};
+
+    #[derive(Debug)]
+    struct Wrapped(std::num::ParseIntError);
+
+    impl Display for Wrapped {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("could not read machine")
+        }
+    }
+
+    impl Error for Wrapped {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).into_outcome(), Outcome::Solved(42));
+        assert_eq!(None::<u64>.into_outcome(), Outcome::Unsolved);
+    }
+
+    #[test]
+    fn converts_results_with_source_chain() {
+        assert_eq!(Ok::<_, String>(7).into_outcome(), Outcome::Solved(7));
+        assert_eq!(
+            Err::<u64, _>("no solution").into_outcome(),
+            Outcome::Failed("no solution".into())
+        );
+        let err = Wrapped("x".parse::<u8>().unwrap_err());
+        assert_eq!(
+            Err::<u64, _>(err).into_outcome(),
+            Outcome::Failed(
+                "could not read machine\n  caused by: invalid digit found in string".into()
+            )
+        );
+    }
+}
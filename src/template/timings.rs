@@ -1,17 +1,110 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::allocations::Allocations;
+use crate::template::counters::Counters;
+use crate::template::environment::{Environment, comparable};
+use crate::template::{Day, data_dir};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+static HISTORY_FILE_NAME: &str = "timings_history.jsonl";
+
+/// Version of the format of `timings.json`. Files without one store formatted durations, e.g.
+/// `"74.1µs"`, and are migrated when read.
+const FORMAT_VERSION: f64 = 2.0;
 
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
+    pub total_nanos: u64,
+    /// The parse stage, for solutions that have one.
+    pub parse: Option<Stats>,
+    /// Whether part 1 and part 2 produced an answer, unknown for timings stored before it was
+    /// recorded.
+    pub status: [Option<PartStatus>; 2],
+    /// Machine and build the timing was measured on, unknown for timings stored before it was
+    /// recorded.
+    pub environment: Option<Environment>,
+}
+
+impl Timing {
+    /// The timing of `part`, 1 or 2.
+    pub fn part(&self, part: u8) -> Option<&Stats> {
+        if part == 1 {
+            self.part_1.as_ref()
+        } else {
+            self.part_2.as_ref()
+        }
+    }
+}
+
+/// Times of a benched stage in nanoseconds. Timings stored before the spread was recorded only
+/// have an average.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub average: u64,
+    pub min: Option<u64>,
+    pub median: Option<u64>,
+    pub samples: Option<u64>,
+    /// What the stage allocated, unknown for timings stored before it was recorded or measured
+    /// with DHAT.
+    pub allocations: Option<Allocations>,
+    /// Hardware counters of a run, only measured by `cargo time --counters`.
+    pub counters: Option<Counters>,
+}
+
+impl Stats {
+    pub fn from_average(average: u64) -> Self {
+        Self {
+            average,
+            ..Self::default()
+        }
+    }
+}
+
+/// Formats nanoseconds like the runner does, e.g. `74.1µs`.
+pub fn format_nanos(nanos: u64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos))
+}
+
+/// Whether a part produced an answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    Failed,
+}
+
+impl PartStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "failed" => Ok(PartStatus::Failed),
+            _ => Err(format!("unknown part status `{s}`.")),
+        }
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -25,19 +118,34 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(data_dir().join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
+    /// Rehydrate timings from a JSON file. If not present, returns empty timings. Files in the
+    /// format before timings were stored as numbers are migrated.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+        let Ok(contents) = fs::read_to_string(data_dir().join(TIMINGS_FILE_NAME)) else {
+            return Timings::default();
+        };
+        let needs_migration = JsonValue::from_str(&contents).is_ok_and(|json| {
+            json.get::<HashMap<String, JsonValue>>()
+                .is_some_and(|map| !map.contains_key("version"))
+        });
+        let Ok(timings) = Timings::try_from(contents) else {
+            return Timings::default();
+        };
+        if needs_migration {
+            match timings.store_file() {
+                Ok(()) => println!("Migrated {TIMINGS_FILE_NAME} to store timings as numbers."),
+                Err(e) => eprintln!("Failed to migrate {TIMINGS_FILE_NAME}: {e}"),
+            }
+        }
+        timings
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings, overwriting `self` with `other` if present. Timings of a day
+    /// measured on another machine or build are kept next to the new ones.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -46,18 +154,35 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| {
+                t.day == timing.day
+                    && comparable(t.environment.as_ref(), timing.environment.as_ref())
+            }) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// The timings that can be compared with ones measured in `environment`.
+    pub fn for_environment(&self, environment: Option<&Environment>) -> Self {
+        Timings {
+            data: self
+                .data
+                .iter()
+                .filter(|t| comparable(t.environment.as_ref(), environment))
+                .cloned()
+                .collect(),
+        }
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = self.data.iter().map(|x| x.total_nanos).sum::<u64>() as f64;
+        nanos / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -67,12 +192,64 @@ impl Timings {
     }
 }
 
+/// A timing as it was stored by an earlier run.
+#[derive(Clone, Debug)]
+pub struct Recorded {
+    /// Seconds since the unix epoch.
+    pub recorded_at: u64,
+    pub timing: Timing,
+}
+
+impl Timings {
+    /// Appends the timings to the history of stored runs, one JSON line per day.
+    pub fn append_history(&self, recorded_at: u64) -> Result<(), Error> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(data_dir().join(HISTORY_FILE_NAME))?;
+        for timing in &self.data {
+            let mut json = JsonValue::from(timing);
+            if let JsonValue::Object(map) = &mut json {
+                #[allow(clippy::cast_precision_loss)]
+                map.insert("recorded_at".into(), JsonValue::Number(recorded_at as f64));
+            }
+            writeln!(file, "{}", json.stringify().map_err(Error::other)?)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the history of stored runs, oldest first. Lines that cannot be read are skipped.
+    /// Lines in the format before timings were stored as numbers are read as well.
+    pub fn read_history() -> Vec<Recorded> {
+        let history = fs::read_to_string(data_dir().join(HISTORY_FILE_NAME)).unwrap_or_default();
+        let mut recorded: Vec<Recorded> = history
+            .lines()
+            .filter_map(|line| {
+                let json = JsonValue::from_str(line).ok()?;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let recorded_at = *json
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("recorded_at")?
+                    .get::<f64>()? as u64;
+                let timing = Timing::try_from(&json).ok()?;
+                Some(Recorded {
+                    recorded_at,
+                    timing,
+                })
+            })
+            .collect();
+        recorded.sort_by_key(|r| r.recorded_at);
+        recorded
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("version".into(), JsonValue::Number(FORMAT_VERSION));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -107,31 +284,47 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
+#[allow(clippy::cast_precision_loss)]
+fn number(nanos: u64) -> JsonValue {
+    JsonValue::Number(nanos as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos(value: &JsonValue) -> Option<u64> {
+    value.get::<f64>().map(|nanos| nanos.round() as u64)
+}
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        map.insert("total_nanos".into(), number(value.total_nanos));
+
+        let stages = [
+            ("parse", &value.parse),
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+        ];
+        for (key, stats) in stages {
+            map.insert(
+                key.into(),
+                stats.as_ref().map_or(JsonValue::Null, Into::into),
+            );
+        }
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (idx, status) in value.status.iter().enumerate() {
+            if let Some(status) = status {
+                map.insert(
+                    format!("part_{}_status", idx + 1),
+                    JsonValue::String(status.as_str().into()),
+                );
+            }
+        }
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        if let Some(environment) = &value.environment {
+            map.insert("environment".into(), environment.into());
+        }
 
         JsonValue::Object(map)
     }
@@ -151,76 +344,296 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let stage = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => Stats::try_from(v)
+                .map(Some)
+                .map_err(|e| format!("timing.{key}: {e}")),
+        };
+        for key in ["part_1", "part_2"] {
+            if !json.contains_key(key) {
+                return Err(format!("Expected timing.{key} to be null or a stage."));
+            }
+        }
+        let mut part_1 = stage("part_1")?;
+        let mut part_2 = stage("part_2")?;
 
         let total_nanos = json
             .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
+            .and_then(nanos)
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let mut status = [None, None];
+        for (idx, part) in [&mut part_1, &mut part_2].into_iter().enumerate() {
+            let key = format!("part_{}_status", idx + 1);
+            status[idx] = json
+                .get(&key)
+                .and_then(|v| v.get::<String>())
+                .map(|s| s.parse())
+                .transpose()?;
+            // before timings were stored as numbers, the spread and status were stored apart.
+            if let Some(details) = json.get(&format!("part_{}_details", idx + 1)) {
+                status[idx] = status[idx].or(migrate_details(details, part.as_mut())?);
+            }
+        }
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1,
+            part_2,
             total_nanos,
+            parse: stage("parse")?,
+            status,
+            environment: json
+                .get("environment")
+                .map(Environment::try_from)
+                .transpose()?,
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("average_nanos".into(), number(value.average));
+        let optional = [
+            ("min_nanos", value.min),
+            ("median_nanos", value.median),
+            ("samples", value.samples),
+            ("allocations", value.allocations.map(|a| a.count)),
+            ("peak_bytes", value.allocations.map(|a| a.peak_bytes)),
+            ("instructions", value.counters.map(|c| c.instructions)),
+            ("cycles", value.counters.map(|c| c.cycles)),
+            ("branch_misses", value.counters.map(|c| c.branch_misses)),
+            ("cache_misses", value.counters.map(|c| c.cache_misses)),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                map.insert(key.into(), number(value));
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        // timings stored before they were numbers only have the formatted average.
+        if let Some(average) = value.get::<String>() {
+            return parse_legacy_duration(average).map(Stats::from_average);
+        }
+
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stage to be a JSON object.")?;
+        let field = |key: &str| json.get(key).and_then(nanos);
+
+        Ok(Stats {
+            average: field("average_nanos")
+                .ok_or("Expected stage.average_nanos to be a number.")?,
+            min: field("min_nanos"),
+            median: field("median_nanos"),
+            samples: field("samples"),
+            allocations: field("allocations").map(|count| Allocations {
+                count,
+                peak_bytes: field("peak_bytes").unwrap_or_default(),
+            }),
+            counters: field("instructions").map(|instructions| Counters {
+                instructions,
+                cycles: field("cycles").unwrap_or_default(),
+                branch_misses: field("branch_misses").unwrap_or_default(),
+                cache_misses: field("cache_misses").unwrap_or_default(),
+            }),
+        })
+    }
+}
+
+/// Reads the spread of a part from the `part_N_details` of timings stored before timings were
+/// numbers into `stats`, returning the status of the part.
+fn migrate_details(
+    details: &JsonValue,
+    stats: Option<&mut Stats>,
+) -> Result<Option<PartStatus>, String> {
+    let json = details
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected part details to be a JSON object.")?;
+    let duration = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.get::<String>())
+            .map(|d| parse_legacy_duration(d))
+            .transpose()
+    };
+
+    if let Some(stats) = stats {
+        stats.min = duration("min")?;
+        stats.median = duration("median")?;
+        stats.samples = json.get("samples").and_then(nanos);
+    }
+
+    json.get("status")
+        .and_then(|v| v.get::<String>())
+        .map(|s| s.parse())
+        .transpose()
+}
+
+/// Nanoseconds in a duration formatted with `{:.1?}`, e.g. `74.1µs`, as timings were stored
+/// before they were numbers.
+fn parse_legacy_duration(s: &str) -> Result<u64, String> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let units = [
+        ("ns", 1_f64),
+        ("µs", 1e3),
+        ("us", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+    ];
+    units
+        .iter()
+        .find_map(|(unit, scale)| {
+            let value: f64 = s.strip_suffix(unit)?.parse().ok()?;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some((value * scale).round() as u64)
+        })
+        .ok_or_else(|| format!("could not read duration `{s}`."))
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{Stats, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(Stats::from_average(10_000_000)),
+                    part_2: Some(Stats::from_average(20_000_000)),
+                    total_nanos: 30_000_000_000,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(Stats::from_average(30_000_000)),
+                    part_2: Some(Stats::from_average(40_000_000)),
+                    total_nanos: 70_000_000_000,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(Stats::from_average(40_000_000)),
                     part_2: None,
-                    total_nanos: 4e+10,
+                    total_nanos: 40_000_000_000,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::allocations::Allocations,
+            template::counters::Counters,
+            template::timings::{PartStatus, Stats, Timings},
+        };
+        use tinyjson::JsonValue;
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "average_nanos": 1000000, "min_nanos": 900000, "median_nanos": 1000000, "samples": 1000, "allocations": 3, "peak_bytes": 1024 }, "part_2": null, "part_1_status": "solved", "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(
+                timing.part_1,
+                Some(Stats {
+                    average: 1_000_000,
+                    min: Some(900_000),
+                    median: Some(1_000_000),
+                    samples: Some(1000),
+                    allocations: Some(Allocations {
+                        count: 3,
+                        peak_bytes: 1024,
+                    }),
+                    counters: None,
+                })
+            );
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.status, [Some(PartStatus::Solved), None]);
+            assert_eq!(timing.total_nanos, 1_000_000_000);
+        }
+
+        #[test]
+        fn migrates_formatted_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2.5µs", "part_1": "1.2ms", "part_2": null, "total_nanos": 1202500.0, "part_1_details": { "min": "900.0µs", "median": "1.1ms", "samples": 1000, "status": "solved" }, "part_2_details": { "status": "unsolved" } }] }"#.to_string();
+            let timing = Timings::try_from(json).unwrap().data.remove(0);
+            assert_eq!(timing.parse, Some(Stats::from_average(2_500)));
+            assert_eq!(
+                timing.part_1,
+                Some(Stats {
+                    average: 1_200_000,
+                    min: Some(900_000),
+                    median: Some(1_100_000),
+                    samples: Some(1000),
+                    allocations: None,
+                    counters: None,
+                })
+            );
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(
+                timing.status,
+                [Some(PartStatus::Solved), Some(PartStatus::Unsolved)]
+            );
+            assert_eq!(timing.total_nanos, 1_202_500);
+        }
+
+        #[test]
+        fn round_trips_stats() {
+            let mut timings = Timings::try_from(
+                r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string(),
+            )
+            .unwrap();
+            assert_eq!(timings.data[0].status, [None, None]);
+
+            timings.data[0].parse = Some(Stats::from_average(2_000_000));
+            timings.data[0].part_1 = Some(Stats {
+                average: 1_000_000,
+                min: Some(900_000),
+                median: Some(1_000_000),
+                samples: Some(1000),
+                allocations: Some(Allocations {
+                    count: 0,
+                    peak_bytes: 0,
+                }),
+                counters: Some(Counters {
+                    instructions: 4_000_000,
+                    cycles: 1_000_000,
+                    branch_misses: 120,
+                    cache_misses: 3,
+                }),
+            });
+            timings.data[0].status = [Some(PartStatus::Solved), Some(PartStatus::Unsolved)];
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data, timings.data);
         }
 
         #[test]
@@ -271,7 +684,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Stats, Timing, Timings},
         };
 
         #[test]
@@ -279,13 +692,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    part_1: Some(Stats::from_average(1_000_000)),
+                    part_2: Some(Stats::from_average(2_000_000)),
+                    total_nanos: 3_000_000_000,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +709,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(Stats::from_average(1_000_000)),
                     part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    total_nanos: 1_000_000_000,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,17 +728,21 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0.0,
+                    total_nanos: 0,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::environment::Environment,
             template::timings::{Timing, Timings},
         };
 
@@ -333,7 +756,10 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    total_nanos: 0,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -353,7 +779,10 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    total_nanos: 0,
+                    parse: None,
+                    status: Default::default(),
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,7 +790,7 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos, 0);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
@@ -380,5 +809,40 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_timings_of_other_environments() {
+            let workstation = Environment {
+                cpu: "AMD Ryzen 9 7950X".into(),
+                cores: 32,
+                rustc: "rustc 1.91.0".into(),
+                profile: "release".into(),
+                target_features: "avx2".into(),
+                allocator: "counting".into(),
+                commit: Some("abc1234".into()),
+                dirty: false,
+            };
+            let timings = get_mock_timings();
+            let mut other = get_mock_timings();
+            other.data.truncate(1);
+            other.data[0].environment = Some(workstation.clone());
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[0].day, day!(1));
+            assert_eq!(merged.data[1].day, day!(1));
+
+            let current = merged.for_environment(Some(&workstation));
+            assert_eq!(current.data.len(), 1);
+            assert!(current.is_day_complete(day!(1)));
+            assert!(!current.is_day_complete(day!(2)));
+
+            let again = Environment {
+                commit: Some("def5678".into()),
+                ..workstation
+            };
+            other.data[0].environment = Some(again);
+            assert_eq!(merged.merge(&other).data.len(), 4);
+        }
     }
 }
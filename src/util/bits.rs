@@ -0,0 +1,393 @@
+//! Bitsets and linear algebra over GF(2).
+
+/// Operations shared by [`BitSet`] and [`DynBitSet`], implemented on top of their words.
+pub trait Bits {
+    fn words(&self) -> &[u64];
+    fn words_mut(&mut self) -> &mut [u64];
+
+    /// Number of bits the set can hold.
+    fn capacity(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.words().iter().all(|&w| w == 0)
+    }
+
+    fn get(&self, idx: usize) -> bool {
+        self.words()[idx / 64] >> (idx % 64) & 1 == 1
+    }
+
+    fn set(&mut self, idx: usize) {
+        self.words_mut()[idx / 64] |= 1 << (idx % 64);
+    }
+
+    fn unset(&mut self, idx: usize) {
+        self.words_mut()[idx / 64] &= !(1 << (idx % 64));
+    }
+
+    fn toggle(&mut self, idx: usize) {
+        self.words_mut()[idx / 64] ^= 1 << (idx % 64);
+    }
+
+    fn count_ones(&self) -> u32 {
+        self.words().iter().map(|w| w.count_ones()).sum()
+    }
+
+    /// XORs `other` into `self`. Both sets must have the same number of words.
+    fn xor_with(&mut self, other: &Self) {
+        for (w, o) in self.words_mut().iter_mut().zip(other.words()) {
+            *w ^= o;
+        }
+    }
+
+    /// Indices of the set bits, in ascending order.
+    fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words().iter().enumerate().flat_map(|(idx, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(idx * 64 + bit)
+            })
+        })
+    }
+}
+
+/// A bitset of `64 * WORDS` bits stored inline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    pub const fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> Bits for BitSet<WORDS> {
+    fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    fn words_mut(&mut self) -> &mut [u64] {
+        &mut self.words
+    }
+
+    fn capacity(&self) -> usize {
+        64 * WORDS
+    }
+}
+
+/// A heap allocated bitset with a length chosen at runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DynBitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl DynBitSet {
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+}
+
+impl Bits for DynBitSet {
+    fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    fn words_mut(&mut self) -> &mut [u64] {
+        &mut self.words
+    }
+
+    fn capacity(&self) -> usize {
+        self.len
+    }
+}
+
+/// The solutions of a linear system over GF(2): every solution is `particular` XOR a
+/// combination of the `null_space` basis vectors.
+#[derive(Debug, Clone)]
+pub struct Gf2Solution {
+    pub particular: DynBitSet,
+    pub null_space: Vec<DynBitSet>,
+}
+
+impl Gf2Solution {
+    /// The solution with the fewest set bits. Enumerates all `2^k` combinations of the null space
+    /// basis in Gray code order, so `k` has to stay small. Returns [`None`] if the null space
+    /// has 64 or more basis vectors, as there are too many combinations to enumerate.
+    pub fn min_weight(&self) -> Option<DynBitSet> {
+        if self.null_space.len() >= 64 {
+            return None;
+        }
+        let mut current = self.particular.clone();
+        let mut best = current.clone();
+        for step in 1..1u64 << self.null_space.len() {
+            current.xor_with(&self.null_space[step.trailing_zeros() as usize]);
+            if current.count_ones() < best.count_ones() {
+                best.clone_from(&current);
+            }
+        }
+        Some(best)
+    }
+}
+
+/// Solves `sum(x[j] * columns[j]) = target` over GF(2), i.e. finds which columns XOR to `target`.
+/// Returns [`None`] if no combination does.
+pub fn solve_gf2<B: Bits>(columns: &[B], target: &B) -> Option<Gf2Solution> {
+    let vars = columns.len();
+    let mut rows: Vec<(DynBitSet, bool)> = (0..target.capacity())
+        .map(|eq| {
+            let mut row = DynBitSet::new(vars);
+            for (var, column) in columns.iter().enumerate() {
+                if column.get(eq) {
+                    row.set(var);
+                }
+            }
+            (row, target.get(eq))
+        })
+        .collect();
+
+    let mut pivots: Vec<usize> = vec![];
+    for var in 0..vars {
+        let rank = pivots.len();
+        let Some(pivot_row) = (rank..rows.len()).find(|&r| rows[r].0.get(var)) else {
+            continue;
+        };
+        rows.swap(rank, pivot_row);
+        let (pivot, pivot_rhs) = rows[rank].clone();
+        for (r, (row, rhs)) in rows.iter_mut().enumerate() {
+            if r != rank && row.get(var) {
+                row.xor_with(&pivot);
+                *rhs ^= pivot_rhs;
+            }
+        }
+        pivots.push(var);
+    }
+    if rows[pivots.len()..].iter().any(|&(_, rhs)| rhs) {
+        return None;
+    }
+
+    let mut particular = DynBitSet::new(vars);
+    for (&var, (_, rhs)) in pivots.iter().zip(&rows) {
+        if *rhs {
+            particular.set(var);
+        }
+    }
+    let null_space = (0..vars)
+        .filter(|var| !pivots.contains(var))
+        .map(|free| {
+            let mut basis = DynBitSet::new(vars);
+            basis.set(free);
+            for (&var, (row, _)) in pivots.iter().zip(&rows) {
+                if row.get(free) {
+                    basis.set(var);
+                }
+            }
+            basis
+        })
+        .collect();
+    Some(Gf2Solution {
+        particular,
+        null_space,
+    })
+}
+
+/// Parallel bit extract: packs the bits of `value` selected by `mask` into the low bits of the
+/// result. Uses the BMI2 instruction when the CPU supports it and a scalar loop otherwise.
+#[inline]
+pub fn pext(value: u32, mask: u32) -> u32 {
+    #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+    {
+        // SAFETY: the binary is only allowed to run on CPUs with BMI2.
+        unsafe { std::arch::x86_64::_pext_u32(value, mask) }
+    }
+    #[cfg(all(target_arch = "x86_64", not(target_feature = "bmi2")))]
+    {
+        if std::is_x86_feature_detected!("bmi2") {
+            // SAFETY: BMI2 support was just detected.
+            unsafe { pext_bmi2(value, mask) }
+        } else {
+            pext_fallback(value, mask)
+        }
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        pext_fallback(value, mask)
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", not(target_feature = "bmi2")))]
+#[target_feature(enable = "bmi2")]
+fn pext_bmi2(value: u32, mask: u32) -> u32 {
+    std::arch::x86_64::_pext_u32(value, mask)
+}
+
+/// Portable version of [`pext`], one loop iteration per set bit of `mask`.
+pub fn pext_fallback(value: u32, mask: u32) -> u32 {
+    let mut result = 0;
+    let mut mask = mask;
+    let mut out_bit = 1;
+    while mask != 0 {
+        let lowest = mask & mask.wrapping_neg();
+        if value & lowest != 0 {
+            result |= out_bit;
+        }
+        out_bit <<= 1;
+        mask ^= lowest;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_of<const W: usize>(bits: &[usize]) -> BitSet<W> {
+        let mut set = BitSet::new();
+        bits.iter().for_each(|&b| set.set(b));
+        set
+    }
+
+    #[test]
+    fn bit_operations() {
+        let mut set = DynBitSet::new(130);
+        assert_eq!(set.words().len(), 3);
+        set.set(0);
+        set.set(64);
+        set.set(129);
+        set.toggle(5);
+        set.toggle(0);
+        assert_eq!(set.iter_ones().collect::<Vec<_>>(), [5, 64, 129]);
+        set.unset(64);
+        assert!(!set.get(64));
+        assert_eq!(set.count_ones(), 2);
+        let mut other = set.clone();
+        other.xor_with(&set);
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn solves_day_10_example() {
+        // [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1)
+        let target = set_of::<1>(&[1, 2]);
+        let buttons = [&[3][..], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]].map(set_of::<1>);
+        let solution = solve_gf2(&buttons, &target).unwrap();
+        assert_eq!(solution.null_space.len(), 2);
+        assert_eq!(solution.min_weight().unwrap().count_ones(), 2);
+    }
+
+    #[test]
+    fn every_null_space_combination_solves_the_system() {
+        let target = set_of::<2>(&[0, 70, 100]);
+        let columns = [
+            &[0, 70][..],
+            &[100],
+            &[0, 100],
+            &[70],
+            &[0, 70, 100],
+            &[3, 70],
+            &[3],
+        ]
+        .map(set_of::<2>);
+        let solution = solve_gf2(&columns, &target).unwrap();
+        for mask in 0..1u32 << solution.null_space.len() {
+            let mut x = solution.particular.clone();
+            for (idx, basis) in solution.null_space.iter().enumerate() {
+                if mask >> idx & 1 == 1 {
+                    x.xor_with(basis);
+                }
+            }
+            let mut result = BitSet::<2>::new();
+            x.iter_ones().for_each(|var| result.xor_with(&columns[var]));
+            assert_eq!(result, target);
+        }
+        assert_eq!(solution.min_weight().unwrap().count_ones(), 1);
+    }
+
+    #[test]
+    fn refuses_to_enumerate_a_null_space_of_64_vectors() {
+        let columns = vec![DynBitSet::new(1); 64];
+        let solution = solve_gf2(&columns, &DynBitSet::new(1)).unwrap();
+        assert_eq!(solution.null_space.len(), 64);
+        assert!(solution.min_weight().is_none());
+    }
+
+    #[test]
+    fn detects_unsolvable_systems() {
+        let columns = [set_of::<1>(&[0, 1]), set_of::<1>(&[2])];
+        assert!(solve_gf2(&columns, &set_of::<1>(&[0])).is_none());
+    }
+
+    #[test]
+    fn pext_fallback_extracts_masked_bits() {
+        assert_eq!(pext_fallback(0b1011_0110, 0b1111_0000), 0b1011);
+        assert_eq!(pext_fallback(0b1011_0110, 0b0101_0101), 0b0110);
+        assert_eq!(pext_fallback(u32::MAX, 0), 0);
+        assert_eq!(pext_fallback(u32::MAX, u32::MAX), u32::MAX);
+        assert_eq!(pext_fallback(0x8000_0001, 0x8000_0001), 0b11);
+        // day 11 labels: the low 5 bits of three ASCII letters.
+        let code = pext_fallback(
+            u32::from_le_bytes(*b"out "),
+            0b00000000_00011111_00011111_00011111,
+        );
+        assert_eq!(code, 21167);
+    }
+
+    #[test]
+    fn pext_matches_fallback() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..10_000 {
+            // xorshift, so both halves of the state get exercised as value and mask.
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let (value, mask) = (state as u32, (state >> 32) as u32);
+            assert_eq!(pext(value, mask), pext_fallback(value, mask));
+        }
+    }
+
+    #[test]
+    fn pext_matches_fallback_on_small_masks() {
+        // every value and mask of 8 bits, at both ends of the word.
+        for shift in [0, 24] {
+            for mask in 0..=0xffu32 {
+                for value in 0..=0xffu32 {
+                    let (value, mask) = (value << shift, mask << shift);
+                    assert_eq!(pext(value, mask), pext_fallback(value, mask));
+                }
+            }
+        }
+    }
+
+    #[cfg(all(target_arch = "x86_64", not(target_feature = "bmi2")))]
+    #[test]
+    fn pext_bmi2_matches_fallback() {
+        if !std::is_x86_feature_detected!("bmi2") {
+            return;
+        }
+        for mask in 0..=0xffu32 {
+            for value in 0..=0xffu32 {
+                let (value, mask) = (value * 0x0101_0101, mask * 0x0100_0101);
+                // SAFETY: BMI2 support was just detected.
+                assert_eq!(
+                    unsafe { pext_bmi2(value, mask) },
+                    pext_fallback(value, mask)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,345 @@
+//! Integer geometry: points, segments and rectilinear polygons.
+use std::cmp::Ordering;
+
+/// A point on the integer plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Cross product of `a - self` and `b - self`.
+    pub fn cross(self, a: Point, b: Point) -> i64 {
+        (a.x - self.x) * (b.y - self.y) - (a.y - self.y) * (b.x - self.x)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Turn direction of three points, with the y axis pointing up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Collinear,
+}
+
+/// Returns the turn direction when walking from `a` through `b` to `c`.
+pub fn orientation(a: Point, b: Point, c: Point) -> Orientation {
+    match a.cross(b, c).cmp(&0) {
+        Ordering::Greater => Orientation::CounterClockwise,
+        Ordering::Less => Orientation::Clockwise,
+        Ordering::Equal => Orientation::Collinear,
+    }
+}
+
+/// A closed line segment between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    pub const fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_axis_aligned(&self) -> bool {
+        self.start.x == self.end.x || self.start.y == self.end.y
+    }
+
+    /// Returns `true` if `p` lies on the segment, endpoints included.
+    pub fn contains(&self, p: Point) -> bool {
+        orientation(self.start, self.end, p) == Orientation::Collinear
+            && p.x >= self.start.x.min(self.end.x)
+            && p.x <= self.start.x.max(self.end.x)
+            && p.y >= self.start.y.min(self.end.y)
+            && p.y <= self.start.y.max(self.end.y)
+    }
+
+    /// Returns `true` if the segments cross at a single point interior to both.
+    /// Touching endpoints and collinear overlaps do not count.
+    pub fn crosses(&self, other: &Segment) -> bool {
+        let o1 = orientation(self.start, self.end, other.start);
+        let o2 = orientation(self.start, self.end, other.end);
+        let o3 = orientation(other.start, other.end, self.start);
+        let o4 = orientation(other.start, other.end, self.end);
+        [o1, o2, o3, o4]
+            .iter()
+            .all(|&o| o != Orientation::Collinear)
+            && o1 != o2
+            && o3 != o4
+    }
+
+    /// Returns `true` if the segments share at least one point.
+    pub fn intersects(&self, other: &Segment) -> bool {
+        self.crosses(other)
+            || self.contains(other.start)
+            || self.contains(other.end)
+            || other.contains(self.start)
+            || other.contains(self.end)
+    }
+}
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Locates `p` relative to the simple polygon with the given vertices, in order.
+/// Runs in O(n), see [`RectilinearPolygon`] for repeated queries.
+pub fn point_in_polygon(p: Point, vertices: &[Point]) -> Location {
+    let mut inside = false;
+    for (idx, &a) in vertices.iter().enumerate() {
+        let b = vertices[(idx + 1) % vertices.len()];
+        if Segment::new(a, b).contains(p) {
+            return Location::Boundary;
+        }
+        if (a.y > p.y) != (b.y > p.y) {
+            let (low, high) = if a.y < b.y { (a, b) } else { (b, a) };
+            if low.cross(high, p) > 0 {
+                inside = !inside;
+            }
+        }
+    }
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// A polygon whose edges are all horizontal or vertical, preprocessed for O(log n) containment
+/// queries on the integer lattice.
+///
+/// Coordinates are compressed so that index `2i + 1` is the `i`-th distinct coordinate and index
+/// `2i` is the open gap before it. Every compressed cell is classified once and a 2D prefix sum
+/// over the cells that hold lattice points outside the polygon answers rectangle queries.
+#[derive(Debug, Clone)]
+pub struct RectilinearPolygon {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    outside: Vec<u32>,
+}
+
+impl RectilinearPolygon {
+    /// Builds the polygon from its vertices, in order. The last vertex connects back to the first.
+    /// Returns [`None`] if there are fewer than 4 vertices or an edge is not axis-aligned.
+    pub fn new(vertices: &[Point]) -> Option<Self> {
+        if vertices.len() < 4 {
+            return None;
+        }
+        let edges: Vec<Segment> = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| Segment::new(a, b))
+            .collect();
+        if !edges.iter().all(Segment::is_axis_aligned) {
+            return None;
+        }
+
+        let mut xs: Vec<i64> = vertices.iter().map(|p| p.x).collect();
+        let mut ys: Vec<i64> = vertices.iter().map(|p| p.y).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        ys.sort_unstable();
+        ys.dedup();
+        let width = 2 * xs.len() + 1;
+        let height = 2 * ys.len() + 1;
+
+        let mut boundary = vec![false; width * height];
+        let mut toggles = vec![false; width * height];
+        for edge in &edges {
+            let (x1, x2) = minmax(compress(&xs, edge.start.x), compress(&xs, edge.end.x));
+            let (y1, y2) = minmax(compress(&ys, edge.start.y), compress(&ys, edge.end.y));
+            for y in y1..=y2 {
+                boundary[y * width + x1..=y * width + x2].fill(true);
+            }
+            // a vertical edge flips the inside parity of the gap rows it spans.
+            if x1 == x2 {
+                for y in (y1 + 1..y2).step_by(2) {
+                    toggles[y * width + x1] ^= true;
+                }
+            }
+        }
+
+        // even-odd fill of the gap cells, scanning each gap row from the left.
+        let mut interior = vec![false; width * height];
+        for y in (0..height).step_by(2) {
+            let mut inside = false;
+            for x in 0..width {
+                inside ^= toggles[y * width + x];
+                interior[y * width + x] = inside;
+            }
+        }
+
+        let mut outside = vec![0u32; (width + 1) * (height + 1)];
+        for y in 0..height {
+            for x in 0..width {
+                // a cell off the boundary lies in the same region as its neighbouring gap cell.
+                let is_outside = !boundary[y * width + x]
+                    && !interior[(y & !1) * width + (x & !1)]
+                    && has_lattice_point(&xs, x)
+                    && has_lattice_point(&ys, y);
+                outside[(y + 1) * (width + 1) + x + 1] = u32::from(is_outside)
+                    + outside[y * (width + 1) + x + 1]
+                    + outside[(y + 1) * (width + 1) + x]
+                    - outside[y * (width + 1) + x];
+            }
+        }
+
+        Some(Self { xs, ys, outside })
+    }
+
+    /// Returns `true` if every lattice point of the axis-aligned rectangle spanned by `a` and `b`
+    /// lies inside the polygon or on its boundary.
+    pub fn contains_rect(&self, a: Point, b: Point) -> bool {
+        let (x1, x2) = minmax(compress(&self.xs, a.x), compress(&self.xs, b.x));
+        let (y1, y2) = minmax(compress(&self.ys, a.y), compress(&self.ys, b.y));
+        let stride = 2 * self.xs.len() + 2;
+        let at = |x: usize, y: usize| self.outside[y * stride + x];
+        at(x2 + 1, y2 + 1) + at(x1, y1) == at(x1, y2 + 1) + at(x2 + 1, y1)
+    }
+
+    /// Returns `true` if `p` lies inside the polygon or on its boundary.
+    pub fn contains(&self, p: Point) -> bool {
+        self.contains_rect(p, p)
+    }
+}
+
+fn compress(coords: &[i64], value: i64) -> usize {
+    match coords.binary_search(&value) {
+        Ok(idx) => 2 * idx + 1,
+        Err(idx) => 2 * idx,
+    }
+}
+
+fn has_lattice_point(coords: &[i64], idx: usize) -> bool {
+    idx % 2 == 1 || idx == 0 || idx == 2 * coords.len() || coords[idx / 2] - coords[idx / 2 - 1] > 1
+}
+
+fn minmax(a: usize, b: usize) -> (usize, usize) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coords: &[(i64, i64)]) -> Vec<Point> {
+        coords.iter().copied().map(Point::from).collect()
+    }
+
+    #[test]
+    fn orientation_of_turns() {
+        let (a, b) = (Point::new(0, 0), Point::new(2, 0));
+        assert_eq!(
+            orientation(a, b, Point::new(1, 1)),
+            Orientation::CounterClockwise
+        );
+        assert_eq!(orientation(a, b, Point::new(1, -1)), Orientation::Clockwise);
+        assert_eq!(orientation(a, b, Point::new(5, 0)), Orientation::Collinear);
+    }
+
+    #[test]
+    fn segment_crossing_and_touching() {
+        let s1 = Segment::new(Point::new(0, 0), Point::new(4, 4));
+        let s2 = Segment::new(Point::new(0, 4), Point::new(4, 0));
+        let s3 = Segment::new(Point::new(4, 4), Point::new(8, 0));
+        let s4 = Segment::new(Point::new(2, 2), Point::new(6, 6));
+        assert!(s1.crosses(&s2));
+        assert!(!s1.crosses(&s3));
+        assert!(s1.intersects(&s3));
+        assert!(!s1.crosses(&s4));
+        assert!(s1.intersects(&s4));
+        assert!(!s2.intersects(&Segment::new(Point::new(5, 5), Point::new(6, 6))));
+    }
+
+    #[test]
+    fn locates_points_in_concave_polygon() {
+        // U shape opening upwards.
+        let u = points(&[
+            (0, 0),
+            (6, 0),
+            (6, 6),
+            (4, 6),
+            (4, 2),
+            (2, 2),
+            (2, 6),
+            (0, 6),
+        ]);
+        assert_eq!(point_in_polygon(Point::new(1, 1), &u), Location::Inside);
+        assert_eq!(point_in_polygon(Point::new(1, 5), &u), Location::Inside);
+        assert_eq!(point_in_polygon(Point::new(3, 4), &u), Location::Outside);
+        assert_eq!(point_in_polygon(Point::new(4, 4), &u), Location::Boundary);
+        assert_eq!(point_in_polygon(Point::new(7, 1), &u), Location::Outside);
+    }
+
+    #[test]
+    fn rejects_non_rectilinear_polygons() {
+        assert!(RectilinearPolygon::new(&points(&[(0, 0), (4, 0), (4, 4)])).is_none());
+        assert!(RectilinearPolygon::new(&points(&[(0, 0), (4, 0), (4, 4), (1, 3)])).is_none());
+    }
+
+    #[test]
+    fn rectangle_queries_match_brute_force() {
+        let u = points(&[
+            (0, 0),
+            (6, 0),
+            (6, 6),
+            (4, 6),
+            (4, 2),
+            (2, 2),
+            (2, 6),
+            (0, 6),
+        ]);
+        let polygon = RectilinearPolygon::new(&u).unwrap();
+        for x1 in -1..=7 {
+            for y1 in -1..=7 {
+                for x2 in x1..=7 {
+                    for y2 in y1..=7 {
+                        let expected = (x1..=x2).all(|x| {
+                            (y1..=y2).all(|y| {
+                                point_in_polygon(Point::new(x, y), &u) != Location::Outside
+                            })
+                        });
+                        let actual = polygon.contains_rect(Point::new(x1, y1), Point::new(x2, y2));
+                        assert_eq!(actual, expected, "({x1},{y1})..({x2},{y2})");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn adjacent_edges_leave_no_lattice_gap() {
+        // two bars joined at the bottom, with a one-wide notch between x=2 and x=3.
+        let polygon = RectilinearPolygon::new(&points(&[
+            (0, 0),
+            (5, 0),
+            (5, 4),
+            (3, 4),
+            (3, 1),
+            (2, 1),
+            (2, 4),
+            (0, 4),
+        ]))
+        .unwrap();
+        assert!(polygon.contains_rect(Point::new(0, 0), Point::new(5, 4)));
+        assert!(polygon.contains(Point::new(1, 3)));
+        assert!(!polygon.contains(Point::new(6, 3)));
+    }
+}
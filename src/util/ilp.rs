@@ -0,0 +1,193 @@
+//! Exact solver for small, bounded integer linear programs.
+//!
+//! The equality system is brought to reduced row echelon form with fraction-free integer row
+//! operations, which is exact elimination over the rationals without a rational type. Every pivot
+//! variable is then an affine function of the free variables, so only the free variables have to
+//! be searched, each within its bounds.
+
+/// Minimises `cost · x` subject to `a x = b` and `0 <= x[i] <= upper[i]` with `x` integral.
+///
+/// Returns the optimal `x`, or [`None`] if the system has no solution within the bounds. The
+/// running time is the product of the ranges of the free variables, so keep `upper` tight.
+pub fn minimise(a: &[Vec<i64>], b: &[i64], cost: &[i64], upper: &[i64]) -> Option<Vec<i64>> {
+    let vars = cost.len();
+    debug_assert!(a.iter().all(|row| row.len() == vars) && upper.len() == vars);
+
+    let mut rows: Vec<Vec<i128>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, &rhs)| row.iter().chain([&rhs]).map(|&v| i128::from(v)).collect())
+        .collect();
+
+    let mut pivots: Vec<usize> = vec![];
+    for col in 0..vars {
+        let rank = pivots.len();
+        let Some(pivot_row) = (rank..rows.len()).find(|&r| rows[r][col] != 0) else {
+            continue;
+        };
+        rows.swap(rank, pivot_row);
+        let pivot = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[col];
+            if r == rank || factor == 0 {
+                continue;
+            }
+            for (v, p) in row.iter_mut().zip(&pivot) {
+                *v = *v * pivot[col] - p * factor;
+            }
+            reduce(row);
+        }
+        pivots.push(col);
+    }
+    // rows past the rank read `0 = rhs` and are only satisfiable with a zero right-hand side.
+    if rows[pivots.len()..].iter().any(|row| row[vars] != 0) {
+        return None;
+    }
+    rows.truncate(pivots.len());
+
+    let free: Vec<usize> = (0..vars).filter(|col| !pivots.contains(col)).collect();
+    // each pivot variable is settled as soon as the last free variable in its row is assigned.
+    let mut settled_at: Vec<Vec<usize>> = vec![vec![]; free.len() + 1];
+    for (r, row) in rows.iter().enumerate() {
+        let depth = free
+            .iter()
+            .rposition(|&f| row[f] != 0)
+            .map_or(0, |idx| idx + 1);
+        settled_at[depth].push(r);
+    }
+
+    let mut search = Search {
+        rows: &rows,
+        pivots: &pivots,
+        free: &free,
+        settled_at: &settled_at,
+        cost,
+        upper,
+        x: vec![0; vars],
+        best: None,
+    };
+    if search.settle(0) {
+        search.run(0);
+    }
+    search.best.map(|(_, x)| x)
+}
+
+fn reduce(row: &mut [i128]) {
+    let divisor = row.iter().fold(0, |acc, &v| gcd(acc, v.abs()));
+    if divisor > 1 {
+        row.iter_mut().for_each(|v| *v /= divisor);
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+struct Search<'a> {
+    rows: &'a [Vec<i128>],
+    pivots: &'a [usize],
+    free: &'a [usize],
+    settled_at: &'a [Vec<usize>],
+    cost: &'a [i64],
+    upper: &'a [i64],
+    x: Vec<i64>,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+impl Search<'_> {
+    fn run(&mut self, depth: usize) {
+        if depth == self.free.len() {
+            let total = self.x.iter().zip(self.cost).map(|(x, c)| x * c).sum();
+            if self.best.as_ref().is_none_or(|(best, _)| total < *best) {
+                self.best = Some((total, self.x.clone()));
+            }
+            return;
+        }
+        let var = self.free[depth];
+        for value in 0..=self.upper[var] {
+            self.x[var] = value;
+            if self.settle(depth + 1) {
+                self.run(depth + 1);
+            }
+        }
+        self.x[var] = 0;
+    }
+
+    /// Solves the pivot variables that depend on nothing past the first `depth` free variables.
+    /// Returns `false` if one of them is fractional or out of bounds.
+    fn settle(&mut self, depth: usize) -> bool {
+        let vars = self.x.len();
+        for &r in &self.settled_at[depth] {
+            let row = &self.rows[r];
+            let pivot = self.pivots[r];
+            let rest: i128 = self
+                .free
+                .iter()
+                .map(|&f| row[f] * i128::from(self.x[f]))
+                .sum();
+            let numerator = row[vars] - rest;
+            if numerator % row[pivot] != 0 {
+                return false;
+            }
+            match i64::try_from(numerator / row[pivot]) {
+                Ok(value) if (0..=self.upper[pivot]).contains(&value) => self.x[pivot] = value,
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::minimise;
+
+    #[test]
+    fn solves_determined_system() {
+        // x + y = 5, x - y = 1
+        let x = minimise(&[vec![1, 1], vec![1, -1]], &[5, 1], &[1, 1], &[10, 10]);
+        assert_eq!(x, Some(vec![3, 2]));
+    }
+
+    #[test]
+    fn rejects_fractional_and_inconsistent_systems() {
+        // 2x = 3
+        assert_eq!(minimise(&[vec![2]], &[3], &[1], &[10]), None);
+        // x + y = 1, 2x + 2y = 3
+        assert_eq!(
+            minimise(&[vec![1, 1], vec![2, 2]], &[1, 3], &[1, 1], &[10, 10]),
+            None
+        );
+        // x = 4 with x <= 3
+        assert_eq!(minimise(&[vec![1]], &[4], &[1], &[3]), None);
+    }
+
+    #[test]
+    fn minimises_over_free_variables() {
+        // the first machine of day 10's example, buttons as columns.
+        let a = vec![
+            vec![0, 0, 0, 0, 1, 1],
+            vec![0, 1, 0, 0, 0, 1],
+            vec![0, 0, 1, 1, 1, 0],
+            vec![1, 1, 0, 1, 0, 0],
+        ];
+        let x = minimise(&a, &[3, 5, 4, 7], &[1; 6], &[7, 5, 4, 4, 3, 3]).unwrap();
+        assert_eq!(x.iter().sum::<i64>(), 10);
+        for (row, rhs) in a.iter().zip([3, 5, 4, 7]) {
+            assert_eq!(row.iter().zip(&x).map(|(a, x)| a * x).sum::<i64>(), rhs);
+        }
+    }
+
+    #[test]
+    fn honours_cost_weights() {
+        // x + y = 4, y costs more than x.
+        assert_eq!(
+            minimise(&[vec![1, 1]], &[4], &[1, 3], &[10, 10]),
+            Some(vec![4, 0])
+        );
+        assert_eq!(
+            minimise(&[vec![1, 1]], &[4], &[3, 1], &[10, 10]),
+            Some(vec![0, 4])
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Sets of closed integer intervals, kept sorted and merged.
+use std::ops::{Add, Sub};
+
+/// Bounds usable in an [`IntervalSet`].
+pub trait Bound: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + From<u8> {}
+
+impl<T> Bound for T where T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8> {}
+
+/// The closed interval `start..=end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        debug_assert!(start <= end, "interval start is past its end");
+        Self { start, end }
+    }
+
+    /// Number of values in the interval.
+    pub fn len(&self) -> T {
+        self.end - self.start + T::from(1)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Returns `true` if the union of both intervals is itself an interval.
+    fn touches(&self, other: &Self) -> bool {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        second.start <= first.end || second.start - first.end == T::from(1)
+    }
+}
+
+/// A set of values stored as sorted, disjoint and non-adjacent closed intervals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `start..=end` to the set, merging it with every interval it overlaps or touches.
+    pub fn insert(&mut self, start: T, end: T) {
+        let mut new = Interval::new(start, end);
+        let first = self
+            .intervals
+            .partition_point(|i| i.end < new.start && !i.touches(&new));
+        let last = first
+            + self.intervals[first..]
+                .iter()
+                .take_while(|i| i.touches(&new))
+                .count();
+        if let Some(i) = self.intervals.get(first).filter(|_| first < last) {
+            new.start = new.start.min(i.start);
+            new.end = new.end.max(self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [new]);
+    }
+
+    /// Sum of the lengths of all intervals.
+    pub fn total_len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::from(0), |acc, i| acc + i.len())
+    }
+
+    /// Returns `true` if `value` is covered by the set, in O(log n).
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end < value);
+        self.intervals.get(idx).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The merged intervals, in ascending order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut a, mut b) = (0, 0);
+        while a < self.intervals.len() && b < other.intervals.len() {
+            let (x, y) = (self.intervals[a], other.intervals[b]);
+            let start = x.start.max(y.start);
+            let end = x.end.min(y.end);
+            if start <= end {
+                intervals.push(Interval::new(start, end));
+            }
+            if x.end < y.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    /// Values in `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let one = T::from(1);
+        let mut intervals = vec![];
+        let mut b = 0;
+        for &x in &self.intervals {
+            let mut start = x.start;
+            let mut covered = false;
+            while b < other.intervals.len() && other.intervals[b].end < start {
+                b += 1;
+            }
+            for y in other.intervals[b..].iter().take_while(|y| y.start <= x.end) {
+                if y.start > start {
+                    intervals.push(Interval::new(start, y.start - one));
+                }
+                if y.end >= x.end {
+                    covered = true;
+                    break;
+                }
+                start = y.end + one;
+            }
+            if !covered {
+                intervals.push(Interval::new(start, x.end));
+            }
+        }
+        Self { intervals }
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    /// Sorts and merges all intervals at once, in O(n log n).
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut sorted: Vec<Interval<T>> = iter.into_iter().collect();
+        sorted.sort_unstable_by_key(|i| i.start);
+        let mut intervals: Vec<Interval<T>> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) if last.touches(&interval) => last.end = last.end.max(interval.end),
+                _ => intervals.push(interval),
+            }
+        }
+        Self { intervals }
+    }
+}
+
+impl<T: Bound> FromIterator<(T, T)> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        iter.into_iter()
+            .map(|(start, end)| Interval::new(start, end))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals.iter().copied().collect()
+    }
+
+    fn pairs(set: &IntervalSet<i64>) -> Vec<(i64, i64)> {
+        set.intervals().iter().map(|i| (i.start, i.end)).collect()
+    }
+
+    #[test]
+    fn merges_overlapping_nested_and_adjacent() {
+        let s = set(&[
+            (10, 14),
+            (3, 5),
+            (16, 20),
+            (12, 18),
+            (6, 6),
+            (30, 40),
+            (32, 33),
+        ]);
+        assert_eq!(pairs(&s), [(3, 6), (10, 20), (30, 40)]);
+        assert_eq!(s.total_len(), 4 + 11 + 11);
+        assert!(s.contains(17));
+        assert!(!s.contains(8));
+    }
+
+    #[test]
+    fn insert_matches_collect() {
+        let input = [
+            (10, 14),
+            (3, 5),
+            (16, 20),
+            (12, 18),
+            (6, 6),
+            (-4, -2),
+            (0, 1),
+            (-1, -1),
+        ];
+        let mut s = IntervalSet::new();
+        for (start, end) in input {
+            s.insert(start, end);
+        }
+        assert_eq!(s, set(&input));
+        assert_eq!(pairs(&s), [(-4, 1), (3, 6), (10, 20)]);
+    }
+
+    #[test]
+    fn membership() {
+        let s = set(&[(3, 5), (10, 20)]);
+        assert!(!s.contains(2));
+        assert!(s.contains(3));
+        assert!(s.contains(5));
+        assert!(!s.contains(7));
+        assert!(s.contains(20));
+        assert!(!s.contains(21));
+        assert!(!IntervalSet::<i64>::new().contains(0));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (28, 28), (40, 50)]);
+        assert_eq!(pairs(&a.union(&b)), [(0, 30), (40, 50)]);
+        assert_eq!(pairs(&a.intersection(&b)), [(5, 10), (20, 25), (28, 28)]);
+        assert_eq!(pairs(&a.difference(&b)), [(0, 4), (26, 27), (29, 30)]);
+        assert_eq!(pairs(&b.difference(&a)), [(11, 19), (40, 50)]);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn handles_extreme_bounds() {
+        let s: IntervalSet<u64> = [(0, 5), (u64::MAX - 1, u64::MAX)].into_iter().collect();
+        assert!(s.contains(u64::MAX));
+        assert_eq!(s.total_len(), 8);
+    }
+}
@@ -1,3 +1,9 @@
+pub mod bits;
+pub mod geometry;
+pub mod ilp;
+pub mod intervals;
+pub mod rng;
+
 pub fn fast_parse<T>(input: &[u8]) -> (T, &[u8])
 where
     T: std::ops::Add<Output = T> + std::ops::Mul<Output = T> + From<u8> + Clone + std::marker::Copy,